@@ -0,0 +1,324 @@
+//! BootROM log, as returned by `LogRead`.
+//!
+//! The log is free form text, one message per line. Lines are matched by
+//! keywords into [`Event`]s, anything not understood is kept as a message.
+//! The keywords are a best guess, not yet checked against a `LogRead` dump of
+//! a BL616 ROM.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::error::ErrorCode;
+
+const FAIL_WORDS: &[&str] = &[
+    "fail", "failed", "error", "err", "mismatch", "invalid", "bad",
+];
+const PASS_WORDS: &[&str] = &["ok", "pass", "passed", "success", "done"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BootSource {
+    Flash,
+    Uart,
+    Usb,
+    Sdio,
+}
+
+impl fmt::Display for BootSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            BootSource::Flash => "flash",
+            BootSource::Uart => "uart",
+            BootSource::Usb => "usb",
+            BootSource::Sdio => "sdio",
+        };
+        f.write_str(s)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    /// Where the ROM boots from
+    BootSource { source: BootSource },
+    /// Boot header magic / crc check
+    HeaderCheck { passed: bool },
+    /// Image hash check
+    HashCheck { passed: bool },
+    /// Image signature check
+    SignCheck { passed: bool },
+    /// Flash parameters in use, `key=value` pairs as logged
+    FlashConfig { params: BTreeMap<String, String> },
+    /// Error code, takes precedence over the checks above
+    Error {
+        code: u16,
+        description: &'static str,
+    },
+    /// Anything else
+    Message,
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = |passed: bool| if passed { "ok" } else { "FAILED" };
+        match self {
+            Event::BootSource { source } => write!(f, "boot source: {}", source),
+            Event::HeaderCheck { passed } => write!(f, "boot header: {}", verdict(*passed)),
+            Event::HashCheck { passed } => write!(f, "image hash: {}", verdict(*passed)),
+            Event::SignCheck { passed } => write!(f, "signature: {}", verdict(*passed)),
+            Event::FlashConfig { params } => {
+                f.write_str("flash config:")?;
+                for (key, value) in params {
+                    write!(f, " {}={}", key, value)?;
+                }
+                Ok(())
+            }
+            Event::Error { code, .. } => write!(f, "error {}", ErrorCode::from_u16(*code)),
+            Event::Message => f.write_str("message"),
+        }
+    }
+}
+
+impl Event {
+    /// Failed check or error code
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Event::HeaderCheck { passed: false }
+                | Event::HashCheck { passed: false }
+                | Event::SignCheck { passed: false }
+                | Event::Error { .. }
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LogEntry {
+    #[serde(flatten)]
+    pub event: Event,
+    /// Log line as read
+    pub text: String,
+}
+
+impl fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.event {
+            Event::Message => write!(f, "  {}", self.text),
+            _ => write!(f, "* {}  [{}]", self.event, self.text),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BootLog {
+    pub entries: Vec<LogEntry>,
+}
+
+impl BootLog {
+    pub fn parse(log: &str) -> Self {
+        let entries = log
+            .lines()
+            .map(|line| line.trim_matches(|c: char| c.is_whitespace() || c == '\0'))
+            .filter(|line| !line.is_empty())
+            .map(|line| LogEntry {
+                event: parse_line(line),
+                text: line.to_string(),
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Last boot source logged
+    pub fn boot_source(&self) -> Option<BootSource> {
+        self.entries
+            .iter()
+            .rev()
+            .find_map(|entry| match entry.event {
+                Event::BootSource { source } => Some(source),
+                _ => None,
+            })
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &LogEntry> {
+        self.entries.iter().filter(|entry| entry.event.is_failure())
+    }
+}
+
+impl fmt::Display for BootLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "{}", entry)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_line(line: &str) -> Event {
+    let lower = line.to_ascii_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .filter(|w| !w.is_empty())
+        .collect();
+    let has = |candidates: &[&str]| words.iter().any(|w| candidates.contains(w));
+
+    if let Some(code) = error_code(&words) {
+        return Event::Error {
+            code,
+            description: ErrorCode::from_u16(code).description(),
+        };
+    }
+
+    let verdict = if has(FAIL_WORDS) {
+        Some(false)
+    } else if has(PASS_WORDS) {
+        Some(true)
+    } else {
+        None
+    };
+    if let Some(passed) = verdict {
+        if has(&["sign", "signature", "ecdsa"]) {
+            return Event::SignCheck { passed };
+        }
+        if has(&["hash", "sha", "sha256"]) {
+            return Event::HashCheck { passed };
+        }
+        if has(&["header", "bootheader", "magic"]) {
+            return Event::HeaderCheck { passed };
+        }
+    }
+
+    if has(&["boot", "bootrom"]) && has(&["from", "mode", "source"]) {
+        let source = words.iter().find_map(|w| match *w {
+            "flash" | "nor" | "xip" => Some(BootSource::Flash),
+            "uart" => Some(BootSource::Uart),
+            "usb" => Some(BootSource::Usb),
+            "sdio" => Some(BootSource::Sdio),
+            _ => None,
+        });
+        if let Some(source) = source {
+            return Event::BootSource { source };
+        }
+    }
+
+    if has(&["flash"]) {
+        let params = key_values(line);
+        if !params.is_empty() {
+            return Event::FlashConfig { params };
+        }
+    }
+
+    Event::Message
+}
+
+/// Hex code following `err`, `error` or `code`, e.g. `error 0x0204`.
+/// Bare digits are counts or addresses as often as codes, only `0x` counts.
+fn error_code(words: &[&str]) -> Option<u16> {
+    words.windows(2).find_map(|pair| match pair {
+        [key, value] if ["err", "error", "code", "errno"].contains(key) => {
+            let digits = value.strip_prefix("0x")?;
+            (digits.len() == 4)
+                .then(|| u16::from_str_radix(digits, 16).ok())
+                .flatten()
+        }
+        _ => None,
+    })
+}
+
+/// `key=value` and `key: value` pairs of a line
+fn key_values(line: &str) -> BTreeMap<String, String> {
+    let mut params = BTreeMap::new();
+    let mut tokens = line
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|t| !t.is_empty())
+        .peekable();
+    while let Some(token) = tokens.next() {
+        let (key, value) = match token.split_once(['=', ':']) {
+            // a label like `cfg:` rather than a key
+            Some((_, "")) if tokens.peek().is_none_or(|t| t.contains(['=', ':'])) => continue,
+            Some((key, "")) => (key, tokens.next().unwrap()),
+            Some(pair) => pair,
+            None => continue,
+        };
+        if !key.is_empty() {
+            params.insert(key.to_string(), value.to_string());
+        }
+    }
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // made up from the ROM's messages, not a capture
+    const LOG: &str = "\
+BootROM v1.0.0
+boot from flash
+flash cfg: jedec_id=0xc84016 io_mode=4 clk_delay=1
+bootheader magic ok
+bootheader crc error 0x0204
+hash check fail
+sign check pass
+\0\0";
+
+    #[test]
+    fn parse() {
+        let log = BootLog::parse(LOG);
+        let events: Vec<_> = log.entries.iter().map(|e| e.event.clone()).collect();
+        assert_eq!(events.len(), 7);
+        assert_eq!(events[0], Event::Message);
+        assert_eq!(log.boot_source(), Some(BootSource::Flash));
+        match &events[2] {
+            Event::FlashConfig { params } => {
+                assert_eq!(params["jedec_id"], "0xc84016");
+                assert_eq!(params["io_mode"], "4");
+                assert_eq!(params.len(), 3);
+            }
+            event => panic!("{:?}", event),
+        }
+        assert_eq!(events[3], Event::HeaderCheck { passed: true });
+        assert_eq!(
+            events[4],
+            Event::Error {
+                code: 0x0204,
+                description: "boot header crc error"
+            }
+        );
+        assert_eq!(events[5], Event::HashCheck { passed: false });
+        assert_eq!(events[6], Event::SignCheck { passed: true });
+        assert_eq!(log.failures().count(), 2);
+
+        assert_eq!(
+            log.entries[4].to_string(),
+            "* error 0204 (boot header crc error)  [bootheader crc error 0x0204]"
+        );
+    }
+
+    #[test]
+    fn error_codes() {
+        let event = |line: &str| BootLog::parse(line).entries[0].event.clone();
+        assert_eq!(
+            event("load error 0x0204"),
+            Event::Error {
+                code: 0x0204,
+                description: "boot header crc error"
+            }
+        );
+        // not codes
+        assert_eq!(event("err cnt 1234"), Event::Message);
+        assert_eq!(event("error 0204"), Event::Message);
+        assert_eq!(event("code 0x12345"), Event::Message);
+    }
+
+    #[test]
+    fn json() {
+        let log = BootLog::parse("boot from uart\nhello");
+        let json = serde_json::to_string(&log).unwrap();
+        assert_eq!(
+            json,
+            r#"{"entries":[{"event":"boot_source","source":"uart","text":"boot from uart"},{"event":"message","text":"hello"}]}"#
+        );
+    }
+}
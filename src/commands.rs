@@ -1,13 +1,18 @@
-use std::{fmt, ops};
+use std::{fmt, ops, time::Duration};
 
 use crate::{
     error::{Error, Result},
+    fw_header::bl616::SpiFlashCfg,
     CRC32,
 };
 
 pub use self::efuse::*;
+pub use self::image::*;
+pub use self::mem::*;
 
 mod efuse;
+mod image;
+mod mem;
 
 pub trait Response: Sized {
     fn from_raw(raw: &[u8]) -> Result<Self>;
@@ -17,12 +22,26 @@ pub trait Response: Sized {
     }
 }
 
+/// Deadline of commands that don't declare their own
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
+
 pub trait Command {
     type Response: Response;
     fn command_id(&self) -> u8;
     fn to_raw(&self) -> Vec<u8> {
         vec![self.command_id(), 0x00, 0x00, 0x00]
     }
+
+    /// How long the device may take before the final ack, `PD` acks included
+    fn timeout(&self) -> Duration {
+        DEFAULT_TIMEOUT
+    }
+
+    /// Short form for logs, e.g. `FlashWrite @0x2000 len 2048`
+    fn describe(&self) -> String {
+        let name = std::any::type_name::<Self>();
+        name.rsplit("::").next().unwrap_or(name).to_string()
+    }
 }
 
 impl Response for () {
@@ -47,6 +66,7 @@ impl Response for String {
     }
 }
 
+/// Payload followed by its crc32, `data` is the payload alone
 #[derive(Debug)]
 pub struct Crc32<T> {
     pub data: T,
@@ -62,7 +82,7 @@ impl<T: Response> Response for Crc32<T> {
         if crc != crc_raw {
             return Err(Error::Checksum);
         }
-        let data = T::from_raw(&raw[4..])?;
+        let data = T::from_raw(&raw[..len - 4])?;
         Ok(Self { data })
     }
 }
@@ -78,6 +98,7 @@ impl<T> ops::Deref for Crc32<T> {
 #[derive(Debug)]
 pub struct GetBootInfo;
 
+#[derive(Clone)]
 pub struct BootInfo {
     pub boot_rom_version: [u8; 4],
     pub sign: u8,
@@ -155,12 +176,28 @@ impl Default for ClockSet {
         }
     }
 }
+impl ClockSet {
+    /// Ask the ROM to switch its UART to `load_speed` after this command
+    pub fn with_load_speed(load_speed: u32) -> Self {
+        Self {
+            load_speed,
+            ..Default::default()
+        }
+    }
+
+    pub fn load_speed(&self) -> u32 {
+        self.load_speed
+    }
+}
 // \x22\xcc\x08\x00\x01\x00\x00\x00\x00\xc2\x01\x00
 impl Command for ClockSet {
     type Response = ();
     fn command_id(&self) -> u8 {
         0x22
     }
+    fn describe(&self) -> String {
+        format!("ClockSet load_speed {}", self.load_speed)
+    }
     fn to_raw(&self) -> Vec<u8> {
         let mut raw = vec![self.command_id(), 0x00, 0x00, 0x00];
 
@@ -193,6 +230,9 @@ impl Command for FlashErase {
     fn command_id(&self) -> u8 {
         0x30
     }
+    fn describe(&self) -> String {
+        format!("FlashErase 0x{:x}..=0x{:x}", self.start, self.end)
+    }
     fn to_raw(&self) -> Vec<u8> {
         let mut raw = vec![self.command_id(), 0x00, 0x00, 0x00];
 
@@ -203,6 +243,24 @@ impl Command for FlashErase {
 
         raw
     }
+    // worst case 4K sector erase time is ~300ms
+    fn timeout(&self) -> Duration {
+        let sectors = self.end.saturating_sub(self.start) / 4096 + 1;
+        DEFAULT_TIMEOUT + Duration::from_millis(300) * sectors
+    }
+}
+
+/// Erase the whole flash, acked with `PD` keep-alives until done
+pub struct FlashChipErase;
+impl Command for FlashChipErase {
+    type Response = ();
+    fn command_id(&self) -> u8 {
+        0x3c
+    }
+    // chip erase takes up to 200s on 16MiB parts
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(300)
+    }
 }
 
 pub struct FlashWrite {
@@ -215,6 +273,46 @@ impl Command for FlashWrite {
     fn command_id(&self) -> u8 {
         0x31
     }
+    fn describe(&self) -> String {
+        format!(
+            "FlashWrite @0x{:x} len {}",
+            self.start_addr,
+            self.data.len()
+        )
+    }
+    fn to_raw(&self) -> Vec<u8> {
+        let mut raw = vec![self.command_id(), 0x00, 0x00, 0x00];
+
+        raw.extend_from_slice(&self.start_addr.to_le_bytes());
+        raw.extend_from_slice(&self.data);
+
+        recalc_checksum(&mut raw);
+
+        raw
+    }
+}
+
+/// Write xz compressed `data`, expanded to flash by the loader.
+///
+/// A stream is sent as consecutive chunks, `start_addr` advancing by the
+/// compressed length as the vendor tool does.
+pub struct FlashDecompressWrite {
+    pub start_addr: u32,
+    // 2K of compressed stream
+    pub data: Vec<u8>,
+}
+impl Command for FlashDecompressWrite {
+    type Response = ();
+    fn command_id(&self) -> u8 {
+        0x3f
+    }
+    fn describe(&self) -> String {
+        format!(
+            "FlashDecompressWrite @0x{:x} len {}",
+            self.start_addr,
+            self.data.len()
+        )
+    }
     fn to_raw(&self) -> Vec<u8> {
         let mut raw = vec![self.command_id(), 0x00, 0x00, 0x00];
 
@@ -225,6 +323,10 @@ impl Command for FlashWrite {
 
         raw
     }
+    // a chunk may expand to many pages to program
+    fn timeout(&self) -> Duration {
+        DEFAULT_TIMEOUT * 5
+    }
 }
 
 pub struct FlashRead {
@@ -236,6 +338,9 @@ impl Command for FlashRead {
     fn command_id(&self) -> u8 {
         0x32
     }
+    fn describe(&self) -> String {
+        format!("FlashRead @0x{:x} len {}", self.start_addr, self.len)
+    }
     fn to_raw(&self) -> Vec<u8> {
         let mut raw = vec![self.command_id(), 0x00, 0x00, 0x00];
 
@@ -256,11 +361,62 @@ impl Command for FlashReadJedecId {
     }
 }
 
+/// Read `len` (1..=4) bytes of status register with SPI command `cmd`,
+/// e.g. 0x05 for SR1 or 0x35 for SR2
 pub struct FlashReadStatusReg {
-    cmd: u32,
-    len: u32,
+    pub cmd: u32,
+    pub len: u32,
+}
+impl Command for FlashReadStatusReg {
+    type Response = Vec<u8>;
+    fn command_id(&self) -> u8 {
+        0x37
+    }
+    fn describe(&self) -> String {
+        format!("FlashReadStatusReg cmd 0x{:02x} len {}", self.cmd, self.len)
+    }
+    fn to_raw(&self) -> Vec<u8> {
+        let mut raw = vec![self.command_id(), 0x00, 0x00, 0x00];
+
+        raw.extend_from_slice(&self.cmd.to_le_bytes());
+        raw.extend_from_slice(&self.len.to_le_bytes());
+
+        recalc_checksum(&mut raw);
+
+        raw
+    }
+}
+
+/// Write `len` bytes of `data` (LSB first) with SPI command `cmd`,
+/// e.g. 0x01 with len 2 for SR1 and SR2
+pub struct FlashWriteStatusReg {
+    pub cmd: u32,
+    pub len: u32,
+    pub data: u32,
+}
+impl Command for FlashWriteStatusReg {
+    type Response = ();
+    fn command_id(&self) -> u8 {
+        0x38
+    }
+    fn describe(&self) -> String {
+        format!(
+            "FlashWriteStatusReg cmd 0x{:02x} len {} data 0x{:x}",
+            self.cmd, self.len, self.data
+        )
+    }
+    fn to_raw(&self) -> Vec<u8> {
+        let mut raw = vec![self.command_id(), 0x00, 0x00, 0x00];
+
+        raw.extend_from_slice(&self.cmd.to_le_bytes());
+        raw.extend_from_slice(&self.len.to_le_bytes());
+        raw.extend_from_slice(&self.data.to_le_bytes());
+
+        recalc_checksum(&mut raw);
+
+        raw
+    }
 }
-// TODO
 
 pub struct FlashWriteCheck;
 impl Command for FlashWriteCheck {
@@ -314,6 +470,16 @@ impl Default for FlashSetPara {
     }
 }
 
+impl FlashSetPara {
+    /// Default pin and clock setup with the given flash config
+    pub fn new(cfg: &SpiFlashCfg) -> Self {
+        Self {
+            flash_para: cfg.to_bytes().to_vec(),
+            ..Default::default()
+        }
+    }
+}
+
 impl Command for FlashSetPara {
     type Response = ();
     fn command_id(&self) -> u8 {
@@ -338,6 +504,34 @@ impl Command for FlashSetPara {
     }
 }
 
+/// SHA-256 of a flash range read through the SPI controller, no XIP setup needed
+pub struct FlashReadSha {
+    pub start_addr: u32,
+    pub len: u32,
+}
+impl Command for FlashReadSha {
+    type Response = Vec<u8>; // 32 byte SHA checksum
+    fn command_id(&self) -> u8 {
+        0x3d
+    }
+    fn describe(&self) -> String {
+        format!("FlashReadSha @0x{:x} len {}", self.start_addr, self.len)
+    }
+    fn timeout(&self) -> Duration {
+        DEFAULT_TIMEOUT + Duration::from_secs(2) * (self.len / (1024 * 1024) + 1)
+    }
+    fn to_raw(&self) -> Vec<u8> {
+        let mut raw = vec![self.command_id(), 0x00, 0x00, 0x00];
+
+        raw.extend_from_slice(&self.start_addr.to_le_bytes());
+        raw.extend_from_slice(&self.len.to_le_bytes());
+
+        recalc_checksum(&mut raw);
+
+        raw
+    }
+}
+
 pub struct FlashXipReadSha {
     pub start_addr: u32,
     pub len: u32,
@@ -347,6 +541,12 @@ impl Command for FlashXipReadSha {
     fn command_id(&self) -> u8 {
         0x3e
     }
+    fn describe(&self) -> String {
+        format!("FlashXipReadSha @0x{:x} len {}", self.start_addr, self.len)
+    }
+    fn timeout(&self) -> Duration {
+        DEFAULT_TIMEOUT + Duration::from_secs(2) * (self.len / (1024 * 1024) + 1)
+    }
     fn to_raw(&self) -> Vec<u8> {
         let mut raw = vec![self.command_id(), 0x00, 0x00, 0x00];
 
@@ -434,4 +634,29 @@ mod tests {
             vec![0x30, 0xf4, 08, 0x00, 0x00, 0x20, 0x00, 0x00, 0x3f, 0x8d, 0x00, 0x00]
         );
     }
+
+    #[test]
+    fn describe() {
+        let write = FlashWrite {
+            start_addr: 0x2000,
+            data: vec![0; 2048],
+        };
+        assert_eq!(write.describe(), "FlashWrite @0x2000 len 2048");
+        assert_eq!(GetChipId.describe(), "GetChipId");
+    }
+
+    #[test]
+    fn crc32_response() {
+        // efuse_read_mac payload of the chip b40ecf35affb, after OK and len
+        let raw = [0xfb, 0xaf, 0x35, 0xcf, 0x0e, 0xb4, 0xcb, 0xc3, 0x69, 0x33];
+        let mac = Crc32::<Vec<u8>>::from_raw(&raw).unwrap();
+        assert_eq!(*mac, raw[..6]);
+
+        let mut bad = raw;
+        bad[0] ^= 1;
+        assert!(matches!(
+            Crc32::<Vec<u8>>::from_raw(&bad),
+            Err(Error::Checksum)
+        ));
+    }
 }
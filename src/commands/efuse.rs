@@ -1,4 +1,57 @@
-use super::{Command, Crc32};
+use super::{recalc_checksum, Command, Crc32};
+
+/// Program efuse bits, the efuse can only turn bits from 0 to 1
+pub struct EfuseWrite {
+    pub start_addr: u32,
+    pub data: Vec<u8>,
+}
+impl Command for EfuseWrite {
+    type Response = ();
+    fn command_id(&self) -> u8 {
+        0x40
+    }
+    fn describe(&self) -> String {
+        format!(
+            "EfuseWrite @0x{:x} len {}",
+            self.start_addr,
+            self.data.len()
+        )
+    }
+    fn to_raw(&self) -> Vec<u8> {
+        let mut raw = vec![self.command_id(), 0x00, 0x00, 0x00];
+
+        raw.extend_from_slice(&self.start_addr.to_le_bytes());
+        raw.extend_from_slice(&self.data);
+
+        recalc_checksum(&mut raw);
+
+        raw
+    }
+}
+
+pub struct EfuseRead {
+    pub start_addr: u32,
+    pub len: u32,
+}
+impl Command for EfuseRead {
+    type Response = Vec<u8>;
+    fn command_id(&self) -> u8 {
+        0x41
+    }
+    fn describe(&self) -> String {
+        format!("EfuseRead @0x{:x} len {}", self.start_addr, self.len)
+    }
+    fn to_raw(&self) -> Vec<u8> {
+        let mut raw = vec![self.command_id(), 0x00, 0x00, 0x00];
+
+        raw.extend_from_slice(&self.start_addr.to_le_bytes());
+        raw.extend_from_slice(&self.len.to_le_bytes());
+
+        recalc_checksum(&mut raw);
+
+        raw
+    }
+}
 
 pub struct EfuseReadMac;
 impl Command for EfuseReadMac {
@@ -0,0 +1,73 @@
+use super::{recalc_checksum, Command};
+
+/// 256 byte `bootheader_t` of the image to load into RAM
+pub struct LoadBootHeader {
+    pub header: Vec<u8>,
+}
+impl Command for LoadBootHeader {
+    type Response = ();
+    fn command_id(&self) -> u8 {
+        0x11
+    }
+    fn to_raw(&self) -> Vec<u8> {
+        let mut raw = vec![self.command_id(), 0x00, 0x00, 0x00];
+        raw.extend_from_slice(&self.header);
+        recalc_checksum(&mut raw);
+        raw
+    }
+}
+
+/// 16 byte segment header: destination, length, reserved, crc32
+pub struct LoadSegHeader {
+    pub header: Vec<u8>,
+}
+impl Command for LoadSegHeader {
+    // the ROM echoes the (decrypted) segment header
+    type Response = Vec<u8>;
+    fn command_id(&self) -> u8 {
+        0x17
+    }
+    fn to_raw(&self) -> Vec<u8> {
+        let mut raw = vec![self.command_id(), 0x00, 0x00, 0x00];
+        raw.extend_from_slice(&self.header);
+        recalc_checksum(&mut raw);
+        raw
+    }
+}
+
+pub struct LoadSegData {
+    pub data: Vec<u8>,
+}
+impl Command for LoadSegData {
+    type Response = ();
+    fn command_id(&self) -> u8 {
+        0x18
+    }
+    fn describe(&self) -> String {
+        format!("LoadSegData len {}", self.data.len())
+    }
+    fn to_raw(&self) -> Vec<u8> {
+        let mut raw = vec![self.command_id(), 0x00, 0x00, 0x00];
+        raw.extend_from_slice(&self.data);
+        recalc_checksum(&mut raw);
+        raw
+    }
+}
+
+/// Verify hash (and signature) of the loaded image
+pub struct CheckImage;
+impl Command for CheckImage {
+    type Response = ();
+    fn command_id(&self) -> u8 {
+        0x19
+    }
+}
+
+/// Jump to the loaded image, the ROM stops answering afterwards
+pub struct RunImage;
+impl Command for RunImage {
+    type Response = ();
+    fn command_id(&self) -> u8 {
+        0x1a
+    }
+}
@@ -0,0 +1,53 @@
+use super::{recalc_checksum, Command};
+
+pub struct MemWrite {
+    pub start_addr: u32,
+    pub data: Vec<u8>,
+}
+impl Command for MemWrite {
+    type Response = ();
+    fn command_id(&self) -> u8 {
+        0x50
+    }
+    fn describe(&self) -> String {
+        format!(
+            "MemWrite @0x{:08x} len {}",
+            self.start_addr,
+            self.data.len()
+        )
+    }
+    fn to_raw(&self) -> Vec<u8> {
+        let mut raw = vec![self.command_id(), 0x00, 0x00, 0x00];
+
+        raw.extend_from_slice(&self.start_addr.to_le_bytes());
+        raw.extend_from_slice(&self.data);
+
+        recalc_checksum(&mut raw);
+
+        raw
+    }
+}
+
+pub struct MemRead {
+    pub start_addr: u32,
+    pub len: u32,
+}
+impl Command for MemRead {
+    type Response = Vec<u8>;
+    fn command_id(&self) -> u8 {
+        0x51
+    }
+    fn describe(&self) -> String {
+        format!("MemRead @0x{:08x} len {}", self.start_addr, self.len)
+    }
+    fn to_raw(&self) -> Vec<u8> {
+        let mut raw = vec![self.command_id(), 0x00, 0x00, 0x00];
+
+        raw.extend_from_slice(&self.start_addr.to_le_bytes());
+        raw.extend_from_slice(&self.len.to_le_bytes());
+
+        recalc_checksum(&mut raw);
+
+        raw
+    }
+}
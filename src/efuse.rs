@@ -0,0 +1,84 @@
+//! Efuse layouts.
+
+pub mod bl616;
+
+/// Efuse array size read by `EfuseRead { start_addr: 0, len: 256 }`
+pub const EFUSE_SIZE: usize = 256;
+
+/// Values packed into a bit range of a 32-bit efuse word
+pub(crate) trait BitField: Copy {
+    fn from_bits(bits: u32) -> Self;
+    fn into_bits(self) -> u32;
+}
+
+impl BitField for bool {
+    fn from_bits(bits: u32) -> Self {
+        bits != 0
+    }
+    fn into_bits(self) -> u32 {
+        self as u32
+    }
+}
+
+impl BitField for u8 {
+    fn from_bits(bits: u32) -> Self {
+        bits as u8
+    }
+    fn into_bits(self) -> u32 {
+        self as u32
+    }
+}
+
+impl BitField for u16 {
+    fn from_bits(bits: u32) -> Self {
+        bits as u16
+    }
+    fn into_bits(self) -> u32 {
+        self as u32
+    }
+}
+
+/// Struct of named fields decoded from one 32-bit word, `field: ty = lo..hi`
+macro_rules! bitfields {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident {
+            $(
+                $(#[$fmeta:meta])*
+                $field:ident: $ty:ty = $lo:literal..$hi:literal,
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+        pub struct $name {
+            $(
+                $(#[$fmeta])*
+                pub $field: $ty,
+            )*
+        }
+
+        impl $name {
+            pub fn from_bits(bits: u32) -> Self {
+                use $crate::efuse::BitField;
+                Self {
+                    $(
+                        $field: <$ty>::from_bits(
+                            (bits >> $lo) & (u32::MAX >> (32 - ($hi - $lo))),
+                        ),
+                    )*
+                }
+            }
+
+            pub fn to_bits(&self) -> u32 {
+                use $crate::efuse::BitField;
+                let mut bits = 0;
+                $(
+                    bits |= (self.$field.into_bits() & (u32::MAX >> (32 - ($hi - $lo)))) << $lo;
+                )*
+                bits
+            }
+        }
+    };
+}
+pub(crate) use bitfields;
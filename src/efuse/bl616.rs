@@ -0,0 +1,317 @@
+//! BL616 efuse map, after `ef_data_0` / `ef_data_1` of the SDK.
+//!
+//! ```text
+//! 0x00 cfg_0            0x5c sw_usage_0..3
+//! 0x04 dbg_pwd          0x6c key_slot_11
+//! 0x14 wifi_mac         0x7c data_0_lock
+//! 0x1c key_slot_0..3    0x80 key_slot_4..10
+//!                       0xfc data_1_lock
+//! ```
+
+use super::{bitfields, EFUSE_SIZE};
+use crate::error::{Error, Result};
+
+/// Number of 128-bit key slots
+pub const KEY_SLOTS: usize = 12;
+
+const CFG_0: usize = 0x00;
+const DBG_PWD: usize = 0x04;
+const WIFI_MAC: usize = 0x14;
+const KEY_SLOT_0: usize = 0x1c;
+const SW_USAGE_0: usize = 0x5c;
+const KEY_SLOT_11: usize = 0x6c;
+const DATA_0_LOCK: usize = 0x7c;
+const KEY_SLOT_4: usize = 0x80;
+const DATA_1_RSVD: usize = 0xf0;
+const DATA_1_LOCK: usize = 0xfc;
+
+bitfields! {
+    /// `ef_cfg_0`, security and debug configuration
+    pub struct Cfg0 {
+        /// Flash encryption, 0: off, 1: AES-128, 2: AES-256, 3: AES-192
+        sf_aes_mode: u8 = 0..2,
+        ai_dis: bool = 2..3,
+        cpu0_dis: bool = 3..4,
+        /// Secure boot (image signature check), one bit per CPU
+        sboot_en: u8 = 4..6,
+        uart_dis: u8 = 6..10,
+        ble2_dis: bool = 10..11,
+        m1542_dis: bool = 11..12,
+        sf_key_re_sel: u8 = 12..14,
+        sdu_dis: bool = 14..15,
+        btdm_dis: bool = 15..16,
+        wifi_dis: bool = 16..17,
+        key_enc_en: bool = 17..18,
+        cam_dis: bool = 18..19,
+        m154_dis: bool = 19..20,
+        cpu1_dis: bool = 20..21,
+        cpu_rst_dbg_dis: bool = 21..22,
+        se_dbg_dis: bool = 22..23,
+        efuse_dbg_dis: bool = 23..24,
+        dbg_jtag_1_dis: u8 = 24..26,
+        /// JTAG, 0: enabled, 1/2: password protected, 3: disabled
+        dbg_jtag_0_dis: u8 = 26..28,
+        dbg_mode: u8 = 28..32,
+    }
+}
+
+bitfields! {
+    /// `ef_sw_usage_0`, BootROM options
+    pub struct SwCfg0 {
+        bootrom_protect: bool = 0..1,
+        uart_log_disable: bool = 1..2,
+        boot_pin_cfg: bool = 2..3,
+        uart_download_cfg: bool = 3..4,
+        mediaboot_disable: bool = 4..5,
+        uartboot_disable: bool = 5..6,
+        usbboot_enable: bool = 6..7,
+        uart_log_reopen: bool = 7..8,
+        /// Signature check of the boot header, see also `Cfg0::sboot_en`
+        sign_cfg: bool = 8..9,
+        dcache_disable: bool = 9..10,
+        jtag_cfg: u8 = 10..12,
+        fix_key_sel: bool = 12..13,
+        sdh_en: bool = 13..14,
+        /// Flash pin configuration, same encoding as `FlashSetPara`
+        sf_pin_cfg: u8 = 14..19,
+        boot_level_shift: bool = 19..20,
+        power_trim_disable: bool = 20..21,
+        trim_enable: bool = 21..22,
+        no_hd_boot_en: bool = 22..23,
+        flash_power_delay: u8 = 23..25,
+        tz_boot: bool = 25..26,
+        encree_tz: bool = 26..27,
+        hbn_check_sign: bool = 27..28,
+        keep_dbg_port_closed: bool = 28..29,
+        hbn_jump_disable: bool = 29..30,
+        rsvd: u8 = 30..32,
+    }
+}
+
+bitfields! {
+    /// `ef_data_0_lock`, write/read protection of the first half
+    pub struct Data0Lock {
+        wr_lock_rsvd: u16 = 0..14,
+        wr_lock_boot_mode: bool = 14..15,
+        wr_lock_dbg_pwd: bool = 15..16,
+        wr_lock_wifi_mac: bool = 16..17,
+        /// Key slots 0..=3, one bit each
+        wr_lock_key_slot_0_3: u8 = 17..21,
+        /// sw_usage_0..=3, one bit each
+        wr_lock_sw_usage: u8 = 21..25,
+        wr_lock_key_slot_11: bool = 25..26,
+        rd_lock_dbg_pwd: bool = 26..27,
+        rd_lock_key_slot_0_3: u8 = 27..31,
+        rd_lock_key_slot_11: bool = 31..32,
+    }
+}
+
+bitfields! {
+    /// `ef_data_1_lock`, write/read protection of the second half
+    pub struct Data1Lock {
+        wr_lock_rsvd: u16 = 0..15,
+        /// Key slots 4..=10, one bit each
+        wr_lock_key_slot_4_10: u8 = 15..22,
+        rsvd: u8 = 22..25,
+        rd_lock_key_slot_4_10: u8 = 25..32,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EfuseMap {
+    pub cfg: Cfg0,
+    /// Debug port password
+    pub dbg_pwd: [u8; 16],
+    /// WiFi MAC as stored, lowest byte first
+    pub wifi_mac: [u8; 6],
+    /// Upper half of `ef_wifi_mac_high`
+    pub wifi_mac_rsvd: u16,
+    /// AES keys and public key hash, indexed by slot number
+    pub key_slots: [[u8; 16]; KEY_SLOTS],
+    /// Raw `ef_sw_usage_0..3`, the first one is decoded into `sw_cfg`
+    pub sw_usage: [u32; 4],
+    pub sw_cfg: SwCfg0,
+    pub data_0_lock: Data0Lock,
+    pub data_1_rsvd: [u8; 12],
+    pub data_1_lock: Data1Lock,
+}
+
+impl EfuseMap {
+    pub fn from_bytes(raw: &[u8]) -> Result<Self> {
+        if raw.len() != EFUSE_SIZE {
+            return Err(Error::Custom(format!(
+                "efuse map needs {} bytes, got {}",
+                EFUSE_SIZE,
+                raw.len()
+            )));
+        }
+        let word = |offset: usize| u32::from_le_bytes(raw[offset..offset + 4].try_into().unwrap());
+
+        let mut key_slots = [[0u8; 16]; KEY_SLOTS];
+        for (key, offset) in key_slots.iter_mut().zip(Self::key_slot_offsets()) {
+            key.copy_from_slice(&raw[offset..offset + 16]);
+        }
+        let mut sw_usage = [0u32; 4];
+        for (i, usage) in sw_usage.iter_mut().enumerate() {
+            *usage = word(SW_USAGE_0 + 4 * i);
+        }
+
+        Ok(Self {
+            cfg: Cfg0::from_bits(word(CFG_0)),
+            dbg_pwd: raw[DBG_PWD..DBG_PWD + 16].try_into().unwrap(),
+            wifi_mac: raw[WIFI_MAC..WIFI_MAC + 6].try_into().unwrap(),
+            wifi_mac_rsvd: u16::from_le_bytes([raw[WIFI_MAC + 6], raw[WIFI_MAC + 7]]),
+            key_slots,
+            sw_usage,
+            sw_cfg: SwCfg0::from_bits(sw_usage[0]),
+            data_0_lock: Data0Lock::from_bits(word(DATA_0_LOCK)),
+            data_1_rsvd: raw[DATA_1_RSVD..DATA_1_RSVD + 12].try_into().unwrap(),
+            data_1_lock: Data1Lock::from_bits(word(DATA_1_LOCK)),
+        })
+    }
+
+    /// Encode back to the efuse array, `sw_cfg` takes precedence over `sw_usage[0]`
+    pub fn to_bytes(&self) -> [u8; EFUSE_SIZE] {
+        let mut raw = [0u8; EFUSE_SIZE];
+        raw[CFG_0..CFG_0 + 4].copy_from_slice(&self.cfg.to_bits().to_le_bytes());
+        raw[DBG_PWD..DBG_PWD + 16].copy_from_slice(&self.dbg_pwd);
+        raw[WIFI_MAC..WIFI_MAC + 6].copy_from_slice(&self.wifi_mac);
+        raw[WIFI_MAC + 6..WIFI_MAC + 8].copy_from_slice(&self.wifi_mac_rsvd.to_le_bytes());
+        for (key, offset) in self.key_slots.iter().zip(Self::key_slot_offsets()) {
+            raw[offset..offset + 16].copy_from_slice(key);
+        }
+        let mut sw_usage = self.sw_usage;
+        sw_usage[0] = self.sw_cfg.to_bits();
+        for (i, usage) in sw_usage.iter().enumerate() {
+            let offset = SW_USAGE_0 + 4 * i;
+            raw[offset..offset + 4].copy_from_slice(&usage.to_le_bytes());
+        }
+        raw[DATA_0_LOCK..DATA_0_LOCK + 4]
+            .copy_from_slice(&self.data_0_lock.to_bits().to_le_bytes());
+        raw[DATA_1_RSVD..DATA_1_RSVD + 12].copy_from_slice(&self.data_1_rsvd);
+        raw[DATA_1_LOCK..DATA_1_LOCK + 4]
+            .copy_from_slice(&self.data_1_lock.to_bits().to_le_bytes());
+        raw
+    }
+
+    /// Efuse offset of a key slot, for `EfuseWrite`. `None` past [`KEY_SLOTS`]
+    pub fn key_slot_offset(slot: usize) -> Option<usize> {
+        let offset = match slot {
+            0..=3 => KEY_SLOT_0 + 16 * slot,
+            4..=10 => KEY_SLOT_4 + 16 * (slot - 4),
+            11 => KEY_SLOT_11,
+            _ => return None,
+        };
+        Some(offset)
+    }
+
+    fn key_slot_offsets() -> impl Iterator<Item = usize> {
+        (0..KEY_SLOTS).filter_map(Self::key_slot_offset)
+    }
+
+    /// `None` past [`KEY_SLOTS`]
+    pub fn is_key_slot_write_locked(&self, slot: usize) -> Option<bool> {
+        let locked = match slot {
+            0..=3 => self.data_0_lock.wr_lock_key_slot_0_3 & (1 << slot) != 0,
+            4..=10 => self.data_1_lock.wr_lock_key_slot_4_10 & (1 << (slot - 4)) != 0,
+            11 => self.data_0_lock.wr_lock_key_slot_11,
+            _ => return None,
+        };
+        Some(locked)
+    }
+
+    /// `None` past [`KEY_SLOTS`]
+    pub fn is_key_slot_read_locked(&self, slot: usize) -> Option<bool> {
+        let locked = match slot {
+            0..=3 => self.data_0_lock.rd_lock_key_slot_0_3 & (1 << slot) != 0,
+            4..=10 => self.data_1_lock.rd_lock_key_slot_4_10 & (1 << (slot - 4)) != 0,
+            11 => self.data_0_lock.rd_lock_key_slot_11,
+            _ => return None,
+        };
+        Some(locked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands;
+    use crate::session::{Session, SyncOptions};
+    use crate::sim::Simulator;
+
+    #[test]
+    fn round_trip() {
+        let mut raw = [0u8; EFUSE_SIZE];
+        for (i, b) in raw.iter_mut().enumerate() {
+            *b = (i as u8).wrapping_mul(37);
+        }
+        let map = EfuseMap::from_bytes(&raw).unwrap();
+        assert_eq!(map.to_bytes(), raw);
+        assert_eq!(map.key_slots[11][..], raw[0x6c..0x7c]);
+        assert_eq!(map.key_slots[4][..], raw[0x80..0x90]);
+
+        assert!(EfuseMap::from_bytes(&raw[..128]).is_err());
+    }
+
+    #[test]
+    fn decode_fields() {
+        let mut raw = [0u8; EFUSE_SIZE];
+        // AES-128, secure boot on CPU0, JTAG disabled
+        raw[0..4].copy_from_slice(&(0x1_u32 | 0x1 << 4 | 0x3 << 26).to_le_bytes());
+        // sf_pin_cfg 0x4
+        raw[0x5c..0x60].copy_from_slice(&(0x4_u32 << 14).to_le_bytes());
+        // read lock on key slot 2, write lock on key slot 5
+        raw[0x7c..0x80].copy_from_slice(&(1_u32 << 29).to_le_bytes());
+        raw[0xfc..0x100].copy_from_slice(&(1_u32 << 16).to_le_bytes());
+
+        let map = EfuseMap::from_bytes(&raw).unwrap();
+        assert_eq!(map.cfg.sf_aes_mode, 1);
+        assert_eq!(map.cfg.sboot_en, 1);
+        assert_eq!(map.cfg.dbg_jtag_0_dis, 3);
+        assert!(!map.cfg.wifi_dis);
+        assert_eq!(map.sw_cfg.sf_pin_cfg, 4);
+        assert_eq!(map.is_key_slot_read_locked(2), Some(true));
+        assert_eq!(map.is_key_slot_write_locked(2), Some(false));
+        assert_eq!(map.is_key_slot_write_locked(5), Some(true));
+        assert_eq!(map.is_key_slot_read_locked(5), Some(false));
+        assert_eq!(map.is_key_slot_read_locked(KEY_SLOTS), None);
+        assert_eq!(EfuseMap::key_slot_offset(11), Some(0x6c));
+        assert_eq!(EfuseMap::key_slot_offset(12), None);
+    }
+
+    #[test]
+    fn read_and_program() {
+        let mut session = Session::connect(Simulator::new(), &SyncOptions::default()).unwrap();
+        let raw = session
+            .send_command(commands::EfuseRead {
+                start_addr: 0,
+                len: EFUSE_SIZE as u32,
+            })
+            .unwrap();
+        let mut map = EfuseMap::from_bytes(&raw).unwrap();
+        assert_eq!(map.wifi_mac, [0xfb, 0xaf, 0x35, 0xcf, 0x0e, 0xb4]);
+
+        map.key_slots[4] = [0x5a; 16];
+        map.data_1_lock.wr_lock_key_slot_4_10 = 0x01;
+        let new = map.to_bytes();
+        let key_slot_4 = EfuseMap::key_slot_offset(4).unwrap();
+        for (offset, len) in [(key_slot_4, 16), (DATA_1_LOCK, 4)] {
+            session
+                .send_command(commands::EfuseWrite {
+                    start_addr: offset as u32,
+                    data: new[offset..offset + len].to_vec(),
+                })
+                .unwrap();
+        }
+
+        let raw = session
+            .send_command(commands::EfuseRead {
+                start_addr: 0,
+                len: EFUSE_SIZE as u32,
+            })
+            .unwrap();
+        let map = EfuseMap::from_bytes(&raw).unwrap();
+        assert_eq!(map.key_slots[4], [0x5a; 16]);
+        assert_eq!(map.is_key_slot_write_locked(4), Some(true));
+    }
+}
@@ -1,5 +1,10 @@
 use thiserror::Error;
 
+pub use self::code::*;
+pub use crate::fw_header::HeaderError;
+
+mod code;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Error, Debug)]
@@ -10,12 +15,44 @@ pub enum Error {
     Io(#[from] std::io::Error),
     #[error("Serial port error: {0}")]
     Serial(#[from] serialport::Error),
-    #[error("BFLB error: {0:04x}")]
-    Code(u16),
-    #[error("Eflash Loader error: {0:04x}")]
-    FlashLoader(u16),
+    #[error("BFLB error: {0}")]
+    Code(ErrorCode),
+    #[error("Eflash Loader error: {0}")]
+    FlashLoader(ErrorCode),
     #[error("UTF8 error: {0}")]
     Utf8(#[from] std::string::FromUtf8Error),
     #[error("CRC checksum error")]
     Checksum,
+    #[error("Timeout waiting for device")]
+    Timeout,
+    #[error("Invalid boot header: {0}")]
+    Header(#[from] HeaderError),
+    #[error("UART sync failed after {0} attempts")]
+    Sync(usize),
+    #[error("Verify failed at region 0x{addr:08x}..0x{end:08x}: sha256 expected {expected}, device {actual}", end = addr + len)]
+    Verify {
+        addr: u32,
+        len: u32,
+        /// Host side SHA-256, hex encoded
+        expected: String,
+        /// Device side SHA-256, hex encoded
+        actual: String,
+    },
+}
+
+impl Error {
+    /// Error code reported by the device, if any
+    pub fn code(&self) -> Option<ErrorCode> {
+        match self {
+            Error::Code(code) | Error::FlashLoader(code) => Some(*code),
+            _ => None,
+        }
+    }
+
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::Checksum | Error::Timeout => true,
+            _ => self.code().is_some_and(|code| code.is_retryable()),
+        }
+    }
 }
@@ -0,0 +1,223 @@
+use std::fmt;
+
+/// Broad class of a BootROM / eflash-loader error code, taken from the high byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    Flash,
+    Command,
+    Image,
+    Interface,
+    Efuse,
+    Memory,
+    Ecdh,
+    System,
+    Unknown,
+}
+
+impl fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            ErrorCategory::Flash => "flash",
+            ErrorCategory::Command => "command",
+            ErrorCategory::Image => "image",
+            ErrorCategory::Interface => "interface",
+            ErrorCategory::Efuse => "efuse",
+            ErrorCategory::Memory => "memory",
+            ErrorCategory::Ecdh => "ecdh",
+            ErrorCategory::System => "system",
+            ErrorCategory::Unknown => "unknown",
+        };
+        f.write_str(s)
+    }
+}
+
+macro_rules! error_codes {
+    ($($variant:ident = $code:literal, $category:ident, $desc:literal;)*) => {
+        /// Error code returned after a `FL` ack, shared by BootROM and eflash loader.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum ErrorCode {
+            $(
+                #[doc = $desc]
+                $variant,
+            )*
+            /// Code not in the catalogue
+            Unknown(u16),
+        }
+
+        impl ErrorCode {
+            pub fn from_u16(code: u16) -> Self {
+                match code {
+                    $($code => ErrorCode::$variant,)*
+                    _ => ErrorCode::Unknown(code),
+                }
+            }
+
+            /// Raw value as sent on the wire
+            pub fn code(&self) -> u16 {
+                match self {
+                    $(ErrorCode::$variant => $code,)*
+                    ErrorCode::Unknown(code) => *code,
+                }
+            }
+
+            /// Human readable description
+            pub fn description(&self) -> &'static str {
+                match self {
+                    $(ErrorCode::$variant => $desc,)*
+                    ErrorCode::Unknown(_) => "unknown error",
+                }
+            }
+
+            pub fn category(&self) -> ErrorCategory {
+                match self {
+                    $(ErrorCode::$variant => ErrorCategory::$category,)*
+                    ErrorCode::Unknown(_) => ErrorCategory::Unknown,
+                }
+            }
+        }
+    };
+}
+
+// From bflb_utils eflash_loader_error_code
+error_codes! {
+    Success = 0x0000, System, "success";
+
+    FlashInit = 0x0001, Flash, "flash init error";
+    FlashEraseParam = 0x0002, Flash, "flash erase parameter error";
+    FlashErase = 0x0003, Flash, "flash erase error";
+    FlashWriteParam = 0x0004, Flash, "flash write parameter error";
+    FlashWriteAddr = 0x0005, Flash, "flash write address error";
+    FlashWrite = 0x0006, Flash, "flash write error";
+    FlashBootParam = 0x0007, Flash, "flash boot parameter error";
+    FlashSetParam = 0x0008, Flash, "flash set parameter error";
+    FlashReadStatusReg = 0x0009, Flash, "flash read status register error";
+    FlashWriteStatusReg = 0x000a, Flash, "flash write status register error";
+    FlashDecompressWrite = 0x000b, Flash, "flash decompress write error";
+    FlashWriteXz = 0x000c, Flash, "flash xz write error";
+    FlashSwitchBank = 0x000d, Flash, "flash switch bank error";
+
+    CmdId = 0x0101, Command, "command id error";
+    CmdLen = 0x0102, Command, "command length error";
+    CmdCrc = 0x0103, Command, "command checksum error";
+    CmdSeq = 0x0104, Command, "command sequence error";
+
+    ImgBootHeaderLen = 0x0201, Image, "boot header length error";
+    ImgBootHeaderNotLoad = 0x0202, Image, "boot header not loaded";
+    ImgBootHeaderMagic = 0x0203, Image, "boot header magic error";
+    ImgBootHeaderCrc = 0x0204, Image, "boot header crc error";
+    ImgBootHeaderEncryptNotFit = 0x0205, Image, "boot header encrypt config does not match efuse";
+    ImgBootHeaderSignNotFit = 0x0206, Image, "boot header sign config does not match efuse";
+    ImgSegmentCnt = 0x0207, Image, "segment count error";
+    ImgAesIvLen = 0x0208, Image, "AES IV length error";
+    ImgAesIvCrc = 0x0209, Image, "AES IV crc error";
+    ImgPkLen = 0x020a, Image, "public key length error";
+    ImgPkCrc = 0x020b, Image, "public key crc error";
+    ImgPkHash = 0x020c, Image, "public key hash error";
+    ImgSignatureLen = 0x020d, Image, "signature length error";
+    ImgSignatureCrc = 0x020e, Image, "signature crc error";
+    ImgSectionHeaderLen = 0x020f, Image, "section header length error";
+    ImgSectionHeaderCrc = 0x0210, Image, "section header crc error";
+    ImgSectionHeaderDst = 0x0211, Image, "section header destination error";
+    ImgSectionDataLen = 0x0212, Image, "section data length error";
+    ImgSectionDataDec = 0x0213, Image, "section data decrypt error";
+    ImgSectionDataTlen = 0x0214, Image, "section data total length error";
+    ImgSectionDataCrc = 0x0215, Image, "section data crc error";
+    ImgHalfBaked = 0x0216, Image, "image half baked";
+    ImgHash = 0x0217, Image, "image hash error";
+    ImgSignParse = 0x0218, Image, "image sign parse error";
+    ImgSign = 0x0219, Image, "image signature error";
+    ImgDec = 0x021a, Image, "image decrypt error";
+    ImgAllInvalid = 0x021b, Image, "all images invalid";
+
+    IfRateLen = 0x0301, Interface, "interface rate length error";
+    IfRateParam = 0x0302, Interface, "interface rate parameter error";
+    IfPassword = 0x0303, Interface, "interface password error";
+    IfPasswordClose = 0x0304, Interface, "interface password closed";
+
+    EfuseWriteParam = 0x0401, Efuse, "efuse write parameter error";
+    EfuseWriteAddr = 0x0402, Efuse, "efuse write address error";
+    EfuseWrite = 0x0403, Efuse, "efuse write error";
+    EfuseReadParam = 0x0404, Efuse, "efuse read parameter error";
+    EfuseReadAddr = 0x0405, Efuse, "efuse read address error";
+    EfuseRead = 0x0406, Efuse, "efuse read error";
+    EfuseReadMac = 0x0407, Efuse, "efuse read mac error";
+    EfuseWriteMac = 0x0408, Efuse, "efuse write mac error";
+
+    MemWriteParam = 0x0501, Memory, "memory write parameter error";
+    MemWriteAddr = 0x0502, Memory, "memory write address error";
+    MemWrite = 0x0503, Memory, "memory write error";
+    MemReadParam = 0x0504, Memory, "memory read parameter error";
+    MemReadAddr = 0x0505, Memory, "memory read address error";
+    MemRead = 0x0506, Memory, "memory read error";
+    RegWriteParam = 0x0508, Memory, "register write parameter error";
+    RegWriteAddr = 0x0509, Memory, "register write address error";
+    RegWrite = 0x050a, Memory, "register write error";
+    RegReadParam = 0x050b, Memory, "register read parameter error";
+    RegReadAddr = 0x050c, Memory, "register read address error";
+    RegRead = 0x050d, Memory, "register read error";
+
+    EcdhParam = 0x0601, Ecdh, "ECDH parameter error";
+    EcdhPrivateKey = 0x0602, Ecdh, "ECDH private key error";
+    EcdhSharedKey = 0x0603, Ecdh, "ECDH shared key error";
+    EcdhRandomVal = 0x0604, Ecdh, "ECDH random value error";
+    EcdhDecrypt = 0x0605, Ecdh, "ECDH decrypt error";
+    EcdhEncrypt = 0x0606, Ecdh, "ECDH encrypt error";
+
+    Pll = 0xfffc, System, "PLL error";
+    Invasion = 0xfffd, System, "invasion detected";
+    Polling = 0xfffe, System, "polling, operation still in progress";
+    Fail = 0xffff, System, "fail";
+}
+
+impl ErrorCode {
+    /// Whether resending the same command has a chance to succeed.
+    ///
+    /// Frame level errors are usually caused by line noise or a lost byte,
+    /// everything else is a real rejection by the device.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ErrorCode::CmdLen | ErrorCode::CmdCrc | ErrorCode::CmdSeq | ErrorCode::Polling
+        )
+    }
+}
+
+impl From<u16> for ErrorCode {
+    fn from(code: u16) -> Self {
+        ErrorCode::from_u16(code)
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04x} ({})", self.code(), self.description())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        for raw in [0x0004, 0x0103, 0x021b, 0x050d, 0xffff] {
+            let code = ErrorCode::from_u16(raw);
+            assert!(!matches!(code, ErrorCode::Unknown(_)));
+            assert_eq!(code.code(), raw);
+        }
+        assert_eq!(ErrorCode::from_u16(0x1234), ErrorCode::Unknown(0x1234));
+        assert_eq!(ErrorCode::Unknown(0x1234).code(), 0x1234);
+    }
+
+    #[test]
+    fn category_and_retry() {
+        assert_eq!(ErrorCode::FlashWriteParam.category(), ErrorCategory::Flash);
+        assert_eq!(ErrorCode::ImgHash.category(), ErrorCategory::Image);
+        assert!(ErrorCode::CmdCrc.is_retryable());
+        assert!(!ErrorCode::FlashWriteAddr.is_retryable());
+        assert_eq!(
+            ErrorCode::FlashWriteParam.to_string(),
+            "0004 (flash write parameter error)"
+        );
+    }
+}
@@ -0,0 +1,32 @@
+//! SPI NOR flash helpers.
+
+pub use self::chips::*;
+pub use self::conf::*;
+pub use self::status::*;
+pub use self::xz::*;
+
+mod chips;
+mod conf;
+mod status;
+mod xz;
+
+/// Largest `FlashWrite` / `FlashDecompressWrite` payload per command
+pub const FLASH_WRITE_CHUNK: usize = 2048;
+
+/// Largest `FlashRead` length per command
+pub const FLASH_READ_CHUNK: u32 = 4096;
+
+/// Smallest unit `FlashErase` clears, ranges are widened to it
+pub const FLASH_SECTOR_SIZE: u32 = 4096;
+
+/// Attempts per `FlashRead` chunk before giving up
+pub const READ_ATTEMPTS: usize = 3;
+
+/// Capacity in bytes from the JEDEC id (manufacturer, type, capacity), if sane
+pub fn capacity_from_jedec(jedec_id: &[u8]) -> Option<u32> {
+    match jedec_id.get(2) {
+        // 64KiB to 2GiB
+        Some(&n @ 0x10..=0x1f) => Some(1 << n),
+        _ => None,
+    }
+}
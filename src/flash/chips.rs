@@ -0,0 +1,237 @@
+use std::fmt;
+
+use super::capacity_from_jedec;
+use crate::fw_header::bl616::SpiFlashCfg;
+
+/// Flash config of the eflash loader, for GigaDevice GD25Q parts
+const GIGADEVICE: &[u8; 84] = include_bytes!("../../chips/bl616/flash_para.bin");
+
+/// Capacity the chip erase time of [`GIGADEVICE`] is given for
+const GIGADEVICE_CAPACITY: u32 = 4 * 1024 * 1024;
+
+/// SPI NOR flash vendor, by JEDEC manufacturer id
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Vendor {
+    GigaDevice,
+    Winbond,
+    /// Also Micron's id
+    Xmc,
+    Puya,
+    Zbit,
+    Boya,
+    Xtx,
+    Macronix,
+    Issi,
+}
+
+impl Vendor {
+    pub fn from_mid(mid: u8) -> Option<Self> {
+        let vendor = match mid {
+            0xc8 => Vendor::GigaDevice,
+            0xef => Vendor::Winbond,
+            0x20 => Vendor::Xmc,
+            0x85 => Vendor::Puya,
+            0x5e => Vendor::Zbit,
+            0x68 => Vendor::Boya,
+            0x0b => Vendor::Xtx,
+            0xc2 => Vendor::Macronix,
+            0x9d => Vendor::Issi,
+            _ => return None,
+        };
+        Some(vendor)
+    }
+
+    pub fn mid(&self) -> u8 {
+        match self {
+            Vendor::GigaDevice => 0xc8,
+            Vendor::Winbond => 0xef,
+            Vendor::Xmc => 0x20,
+            Vendor::Puya => 0x85,
+            Vendor::Zbit => 0x5e,
+            Vendor::Boya => 0x68,
+            Vendor::Xtx => 0x0b,
+            Vendor::Macronix => 0xc2,
+            Vendor::Issi => 0x9d,
+        }
+    }
+
+    /// Generic config for the vendor's quad SPI parts up to 16MiB.
+    ///
+    /// Only GigaDevice's is the eflash loader's own. The others are GD25Q's
+    /// commands and timings with the vendor's id, the QE bit moved where the
+    /// vendor keeps it and continuous read off. They have not been checked
+    /// against datasheets, a vendor `.conf` is the better source.
+    pub fn config(&self) -> SpiFlashCfg {
+        let mut cfg = SpiFlashCfg::from_bytes(GIGADEVICE);
+        if *self == Vendor::GigaDevice {
+            return cfg;
+        }
+        cfg.mid = self.mid();
+        cfg.c_read_support = 0;
+        match self {
+            // QE is SR2 bit 1, SR1 and SR2 written together with 0x01
+            Vendor::GigaDevice
+            | Vendor::Winbond
+            | Vendor::Xmc
+            | Vendor::Puya
+            | Vendor::Zbit
+            | Vendor::Boya
+            | Vendor::Xtx => {}
+            // QE is SR1 bit 6, written alone
+            Vendor::Macronix | Vendor::Issi => {
+                cfg.qe_index = 0;
+                cfg.qe_bit = 6;
+                cfg.qe_write_reg_len = 1;
+                cfg.qe_read_reg_len = 1;
+            }
+        }
+        cfg
+    }
+}
+
+impl fmt::Display for Vendor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Vendor::GigaDevice => "GigaDevice",
+            Vendor::Winbond => "Winbond",
+            Vendor::Xmc => "XMC",
+            Vendor::Puya => "Puya",
+            Vendor::Zbit => "Zbit",
+            Vendor::Boya => "Boya",
+            Vendor::Xtx => "XTX",
+            Vendor::Macronix => "Macronix",
+            Vendor::Issi => "ISSI",
+        };
+        f.write_str(s)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlashChip {
+    pub vendor: Vendor,
+    pub name: &'static str,
+    /// Manufacturer, memory type, capacity
+    pub jedec_id: [u8; 3],
+}
+
+const fn chip(vendor: Vendor, name: &'static str, jedec_id: u32) -> FlashChip {
+    let [_, mid, ty, cap] = jedec_id.to_be_bytes();
+    FlashChip {
+        vendor,
+        name,
+        jedec_id: [mid, ty, cap],
+    }
+}
+
+/// Parts [`GIGADEVICE`] is written for, by JEDEC id. Others get their
+/// vendor's generic config.
+pub const FLASH_CHIPS: &[FlashChip] = &[
+    chip(Vendor::GigaDevice, "GD25Q80E", 0xc84014),
+    chip(Vendor::GigaDevice, "GD25Q16E", 0xc84015),
+    chip(Vendor::GigaDevice, "GD25Q32E", 0xc84016),
+    chip(Vendor::GigaDevice, "GD25Q64E", 0xc84017),
+    chip(Vendor::GigaDevice, "GD25Q128E", 0xc84018),
+];
+
+impl FlashChip {
+    /// Part matching the first three bytes of `jedec_id`
+    pub fn lookup(jedec_id: &[u8]) -> Option<&'static FlashChip> {
+        let id = jedec_id.get(..3)?;
+        FLASH_CHIPS.iter().find(|chip| chip.jedec_id[..] == *id)
+    }
+
+    pub fn capacity(&self) -> u32 {
+        1 << self.jedec_id[2]
+    }
+
+    pub fn config(&self) -> SpiFlashCfg {
+        with_capacity(self.vendor.config(), self.capacity())
+    }
+}
+
+/// Chip erase time scaled up from [`GIGADEVICE_CAPACITY`] to `capacity`
+fn with_capacity(mut cfg: SpiFlashCfg, capacity: u32) -> SpiFlashCfg {
+    if capacity > GIGADEVICE_CAPACITY {
+        let time_ce = cfg.time_ce as u32 * (capacity / GIGADEVICE_CAPACITY);
+        cfg.time_ce = time_ce.min(u16::MAX as u32) as u16;
+    }
+    cfg
+}
+
+impl fmt::Display for FlashChip {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} ({} KiB)",
+            self.vendor,
+            self.name,
+            self.capacity() / 1024
+        )
+    }
+}
+
+/// Config for the flash answering `jedec_id`: the part's own, else its
+/// vendor's, else the eflash loader default
+pub fn flash_config(jedec_id: &[u8]) -> SpiFlashCfg {
+    if let Some(chip) = FlashChip::lookup(jedec_id) {
+        return chip.config();
+    }
+    let cfg = match jedec_id.first().copied().and_then(Vendor::from_mid) {
+        Some(vendor) => vendor.config(),
+        None => SpiFlashCfg::from_bytes(GIGADEVICE),
+    };
+    match capacity_from_jedec(jedec_id) {
+        Some(capacity) => with_capacity(cfg, capacity),
+        None => cfg,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup() {
+        let chip = FlashChip::lookup(&[0xc8, 0x40, 0x16, 0x00]).unwrap();
+        assert_eq!(chip.name, "GD25Q32E");
+        assert_eq!(chip.to_string(), "GigaDevice GD25Q32E (4096 KiB)");
+        // the eflash loader default is meant for it
+        assert_eq!(chip.config().to_bytes(), *GIGADEVICE);
+
+        assert_eq!(
+            FlashChip::lookup(&[0xc8, 0x40, 0x18]).unwrap().name,
+            "GD25Q128E"
+        );
+        assert!(FlashChip::lookup(&[0xc8, 0x40]).is_none());
+        assert!(FlashChip::lookup(&[0xef, 0x40, 0x18]).is_none());
+        assert!(FlashChip::lookup(&[0x01, 0x02, 0x16]).is_none());
+
+        for chip in FLASH_CHIPS {
+            assert_eq!(capacity_from_jedec(&chip.jedec_id), Some(chip.capacity()));
+            assert_eq!(Vendor::from_mid(chip.jedec_id[0]), Some(chip.vendor));
+            assert_eq!(chip.config().mid, chip.jedec_id[0]);
+            let dups = FLASH_CHIPS.iter().filter(|c| c.jedec_id == chip.jedec_id);
+            assert_eq!(dups.count(), 1, "{}", chip);
+        }
+    }
+
+    #[test]
+    fn configs() {
+        let cfg = flash_config(&[0xc2, 0x20, 0x16]);
+        assert_eq!((cfg.qe_index, cfg.qe_bit, cfg.qe_write_reg_len), (0, 6, 1));
+        assert_eq!(cfg.mid, 0xc2);
+        assert_eq!(cfg.c_read_support, 0);
+
+        let cfg = flash_config(&[0x85, 0x60, 0x16]);
+        assert_eq!(cfg.mid, 0x85);
+        assert_eq!((cfg.qe_index, cfg.qe_bit, cfg.qe_write_reg_len), (1, 1, 2));
+        assert_eq!(cfg.time_ce, 33_000);
+
+        // chip erase takes longer on bigger parts
+        assert_eq!(flash_config(&[0xc8, 0x40, 0x15]).time_ce, 33_000);
+        assert_eq!(flash_config(&[0xc8, 0x40, 0x17]).time_ce, 65_535);
+        assert_eq!(flash_config(&[0xef, 0x40, 0x18]).time_ce, 65_535);
+
+        assert_eq!(flash_config(&[0x12, 0x34, 0x56]).to_bytes(), *GIGADEVICE);
+    }
+}
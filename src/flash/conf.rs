@@ -0,0 +1,258 @@
+//! Vendor flash `.conf` files, `[FLASH_CFG]` sections of `key = value` lines
+//! as shipped per flash part with the Bouffalo SDK.
+//!
+//! The key names have not been checked against a file from the SDK yet, the
+//! tests only cover files written by [`SpiFlashCfg::to_conf`].
+
+use std::fmt::Write;
+
+use crate::error::{Error, Result};
+use crate::fw_header::bl616::SpiFlashCfg;
+
+pub const FLASH_CFG_SECTION: &str = "FLASH_CFG";
+
+enum Field<'a> {
+    U8(&'a mut u8),
+    U16(&'a mut u16),
+}
+
+/// `.conf` key of every `SpiFlashCfg` field, in layout order
+fn fields(cfg: &mut SpiFlashCfg) -> [(&'static str, Field<'_>); 78] {
+    use Field::{U16, U8};
+    let [read_reg_0, read_reg_1, read_reg_2, read_reg_3] = &mut cfg.read_reg_cmd;
+    let [write_reg_0, write_reg_1, write_reg_2, write_reg_3] = &mut cfg.write_reg_cmd;
+    [
+        ("io_mode", U8(&mut cfg.io_mode)),
+        ("cont_read_support", U8(&mut cfg.c_read_support)),
+        ("sfctrl_clk_delay", U8(&mut cfg.clk_delay)),
+        ("sfctrl_clk_invert", U8(&mut cfg.clk_invert)),
+        ("reset_en_cmd", U8(&mut cfg.reset_en_cmd)),
+        ("reset_cmd", U8(&mut cfg.reset_cmd)),
+        ("exit_contread_cmd", U8(&mut cfg.reset_cread_cmd)),
+        ("exit_contread_cmd_size", U8(&mut cfg.reset_cread_cmd_size)),
+        ("jedecid_cmd", U8(&mut cfg.jedec_id_cmd)),
+        ("jedecid_cmd_dmy_clk", U8(&mut cfg.jedec_id_cmd_dmy_clk)),
+        ("enter_32bits_addr_cmd", U8(&mut cfg.enter_32bits_addr_cmd)),
+        ("exit_32bits_addr_cmd", U8(&mut cfg.exit_32bits_addr_cmd)),
+        ("sector_size", U8(&mut cfg.sector_size)),
+        ("mfg_id", U8(&mut cfg.mid)),
+        ("page_size", U16(&mut cfg.page_size)),
+        ("chip_erase_cmd", U8(&mut cfg.chip_erase_cmd)),
+        ("sector_erase_cmd", U8(&mut cfg.sector_erase_cmd)),
+        ("blk32k_erase_cmd", U8(&mut cfg.blk32_erase_cmd)),
+        ("blk64k_erase_cmd", U8(&mut cfg.blk64_erase_cmd)),
+        ("write_enable_cmd", U8(&mut cfg.write_enable_cmd)),
+        ("page_prog_cmd", U8(&mut cfg.page_program_cmd)),
+        ("qpage_prog_cmd", U8(&mut cfg.qpage_program_cmd)),
+        ("qual_page_prog_addr_mode", U8(&mut cfg.qpp_addr_mode)),
+        ("fast_read_cmd", U8(&mut cfg.fast_read_cmd)),
+        ("fast_read_dmy_clk", U8(&mut cfg.fr_dmy_clk)),
+        ("qpi_fast_read_cmd", U8(&mut cfg.qpi_fast_read_cmd)),
+        ("qpi_fast_read_dmy_clk", U8(&mut cfg.qpi_fr_dmy_clk)),
+        ("fast_read_do_cmd", U8(&mut cfg.fast_read_do_cmd)),
+        ("fast_read_do_dmy_clk", U8(&mut cfg.fr_do_dmy_clk)),
+        ("fast_read_dio_cmd", U8(&mut cfg.fast_read_dio_cmd)),
+        ("fast_read_dio_dmy_clk", U8(&mut cfg.fr_dio_dmy_clk)),
+        ("fast_read_qo_cmd", U8(&mut cfg.fast_read_qo_cmd)),
+        ("fast_read_qo_dmy_clk", U8(&mut cfg.fr_qo_dmy_clk)),
+        ("fast_read_qio_cmd", U8(&mut cfg.fast_read_qio_cmd)),
+        ("fast_read_qio_dmy_clk", U8(&mut cfg.fr_qio_dmy_clk)),
+        ("qpi_fast_read_qio_cmd", U8(&mut cfg.qpi_fast_read_qio_cmd)),
+        ("qpi_fast_read_qio_dmy_clk", U8(&mut cfg.qpi_fr_qio_dmy_clk)),
+        ("qpi_page_prog_cmd", U8(&mut cfg.qpi_page_program_cmd)),
+        ("write_vreg_enable_cmd", U8(&mut cfg.write_vreg_enable_cmd)),
+        ("wel_reg_index", U8(&mut cfg.wr_enable_index)),
+        ("qe_reg_index", U8(&mut cfg.qe_index)),
+        ("busy_reg_index", U8(&mut cfg.busy_index)),
+        ("wel_bit_pos", U8(&mut cfg.wr_enable_bit)),
+        ("qe_bit_pos", U8(&mut cfg.qe_bit)),
+        ("busy_bit_pos", U8(&mut cfg.busy_bit)),
+        ("wel_reg_write_len", U8(&mut cfg.wr_enable_write_reg_len)),
+        ("wel_reg_read_len", U8(&mut cfg.wr_enable_read_reg_len)),
+        ("qe_reg_write_len", U8(&mut cfg.qe_write_reg_len)),
+        ("qe_reg_read_len", U8(&mut cfg.qe_read_reg_len)),
+        ("release_power_down", U8(&mut cfg.release_power_down)),
+        ("busy_reg_read_len", U8(&mut cfg.busy_read_reg_len)),
+        ("reg_read_cmd0", U8(read_reg_0)),
+        ("reg_read_cmd1", U8(read_reg_1)),
+        ("reg_read_cmd2", U8(read_reg_2)),
+        ("reg_read_cmd3", U8(read_reg_3)),
+        ("reg_write_cmd0", U8(write_reg_0)),
+        ("reg_write_cmd1", U8(write_reg_1)),
+        ("reg_write_cmd2", U8(write_reg_2)),
+        ("reg_write_cmd3", U8(write_reg_3)),
+        ("enter_qpi_cmd", U8(&mut cfg.enter_qpi)),
+        ("exit_qpi_cmd", U8(&mut cfg.exit_qpi)),
+        ("cont_read_code", U8(&mut cfg.c_read_mode)),
+        ("cont_read_exit_code", U8(&mut cfg.c_r_exit)),
+        ("burst_wrap_cmd", U8(&mut cfg.burst_wrap_cmd)),
+        ("burst_wrap_dmy_clk", U8(&mut cfg.burst_wrap_cmd_dmy_clk)),
+        ("burst_wrap_data_mode", U8(&mut cfg.burst_wrap_data_mode)),
+        ("burst_wrap_code", U8(&mut cfg.burst_wrap_data)),
+        ("de_burst_wrap_cmd", U8(&mut cfg.de_burst_wrap_cmd)),
+        (
+            "de_burst_wrap_cmd_dmy_clk",
+            U8(&mut cfg.de_burst_wrap_cmd_dmy_clk),
+        ),
+        (
+            "de_burst_wrap_code_mode",
+            U8(&mut cfg.de_burst_wrap_data_mode),
+        ),
+        ("de_burst_wrap_code", U8(&mut cfg.de_burst_wrap_data)),
+        ("sector_erase_time", U16(&mut cfg.time_e_sector)),
+        ("blk32k_erase_time", U16(&mut cfg.time_e32k)),
+        ("blk64k_erase_time", U16(&mut cfg.time_e64k)),
+        ("page_prog_time", U16(&mut cfg.time_page_pgm)),
+        ("chip_erase_time", U16(&mut cfg.time_ce)),
+        ("power_down_delay", U8(&mut cfg.pd_delay)),
+        ("qe_data", U8(&mut cfg.qe_data)),
+    ]
+}
+
+/// Only found in some files, zero when missing
+const OPTIONAL: &[&str] = &[
+    "reg_read_cmd2",
+    "reg_read_cmd3",
+    "reg_write_cmd2",
+    "reg_write_cmd3",
+];
+
+/// Decimal or 0x-prefixed hex
+fn parse_int(s: &str) -> Option<u32> {
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
+
+impl SpiFlashCfg {
+    /// Parse the `[FLASH_CFG]` section of a vendor `.conf` file. Every field
+    /// must be given, unknown keys are ignored with a warning.
+    pub fn from_conf(conf: &str) -> Result<Self> {
+        let mut cfg = SpiFlashCfg::from_bytes(&[0; SpiFlashCfg::LEN]);
+        let mut seen = vec![];
+        let mut in_section = false;
+        let mut found_section = false;
+        let err =
+            |n: usize, msg: String| Error::Custom(format!("flash conf line {}: {}", n + 1, msg));
+
+        for (n, line) in conf.lines().enumerate() {
+            let line = line.split(['#', ';']).next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(section) = line.strip_prefix('[') {
+                let section = section
+                    .strip_suffix(']')
+                    .ok_or_else(|| err(n, format!("bad section header {:?}", line)))?;
+                in_section = section.trim().eq_ignore_ascii_case(FLASH_CFG_SECTION);
+                found_section |= in_section;
+                continue;
+            }
+            if !in_section {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| err(n, format!("expected key = value, got {:?}", line)))?;
+            let (key, value) = (key.trim(), value.trim());
+            let val = parse_int(value)
+                .ok_or_else(|| err(n, format!("{}: bad number {:?}", key, value)))?;
+            let mut fields = fields(&mut cfg);
+            let Some((key, field)) = fields.iter_mut().find(|(k, _)| k.eq_ignore_ascii_case(key))
+            else {
+                log::warn!("flash conf line {}: unknown key {:?}, ignored", n + 1, key);
+                continue;
+            };
+            let ok = match field {
+                Field::U8(f) => u8::try_from(val).map(|v| **f = v).is_ok(),
+                Field::U16(f) => u16::try_from(val).map(|v| **f = v).is_ok(),
+            };
+            if !ok {
+                return Err(err(n, format!("{} = {} out of range", key, value)));
+            }
+            seen.push(*key);
+        }
+
+        if !found_section {
+            return Err(Error::Custom(format!(
+                "flash conf: no [{}] section",
+                FLASH_CFG_SECTION
+            )));
+        }
+        let mut copy = cfg;
+        let missing: Vec<_> = fields(&mut copy)
+            .iter()
+            .map(|(key, _)| *key)
+            .filter(|key| !seen.contains(key) && !OPTIONAL.contains(key))
+            .collect();
+        if !missing.is_empty() {
+            return Err(Error::Custom(format!(
+                "flash conf: missing {}",
+                missing.join(", ")
+            )));
+        }
+        Ok(cfg)
+    }
+
+    /// Vendor `.conf` form, accepted by [`SpiFlashCfg::from_conf`]
+    pub fn to_conf(&self) -> String {
+        let mut conf = format!("[{}]\n", FLASH_CFG_SECTION);
+        let mut copy = *self;
+        for (key, field) in fields(&mut copy) {
+            let _ = match field {
+                Field::U8(val) if *val == 0 && OPTIONAL.contains(&key) => continue,
+                Field::U8(val) => writeln!(conf, "{} = 0x{:02x}", key, val),
+                Field::U16(val) => writeln!(conf, "{} = {}", key, val),
+            };
+        }
+        conf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FLASH_PARA: &[u8; 84] = include_bytes!("../../chips/bl616/flash_para.bin");
+
+    #[test]
+    fn round_trip() {
+        let cfg = SpiFlashCfg::from_bytes(FLASH_PARA);
+        let conf = cfg.to_conf();
+        assert!(conf.starts_with("[FLASH_CFG]\nio_mode = 0x04\n"));
+        assert!(conf.contains("\nmfg_id = 0xc8\n"));
+        assert!(conf.contains("\nchip_erase_time = 33000\n"));
+        assert!(!conf.contains("reg_read_cmd2"));
+        assert_eq!(conf.lines().count(), 1 + 78 - 4);
+
+        let parsed = SpiFlashCfg::from_conf(&conf).unwrap();
+        assert_eq!(parsed.to_bytes(), *FLASH_PARA);
+    }
+
+    /// Comments, other sections, case and number forms, our output otherwise
+    #[test]
+    fn syntax() {
+        let mut conf =
+            String::from("; GD25Q32E\n[BOOTHEADER_CFG]\nio_mode = 0x11\n\n[FLASH_CFG] \n");
+        conf.push_str(
+            &SpiFlashCfg::from_bytes(FLASH_PARA)
+                .to_conf()
+                .replace("[FLASH_CFG]\n", ""),
+        );
+        let conf = conf
+            .replace("io_mode = 0x04", "IO_MODE = 4   # QIO")
+            .replace("page_size = 256", "page_size = 0x100\nflashcfg_crc32 = 0");
+        let cfg = SpiFlashCfg::from_conf(&conf).unwrap();
+        assert_eq!(cfg.to_bytes(), *FLASH_PARA);
+
+        let err = SpiFlashCfg::from_conf(&conf.replace("mfg_id = 0xc8", "mfg_id = 0x1c8"));
+        assert!(err
+            .unwrap_err()
+            .to_string()
+            .contains("mfg_id = 0x1c8 out of range"));
+        let err = SpiFlashCfg::from_conf(&conf.replace("qe_data = 0x00\n", ""));
+        assert!(err.unwrap_err().to_string().ends_with("missing qe_data"));
+        assert!(SpiFlashCfg::from_conf("io_mode = 4").is_err());
+    }
+}
@@ -0,0 +1,209 @@
+use std::fmt;
+use std::ops::Range;
+
+use super::Vendor;
+
+/// SPI commands for status register access
+pub const READ_STATUS_REG_1: u32 = 0x05;
+pub const READ_STATUS_REG_2: u32 = 0x35;
+/// Writes SR1, and SR2 too when two bytes follow
+pub const WRITE_STATUS_REG: u32 = 0x01;
+
+/// SR1 (low byte) and SR2 (high byte) of a GigaDevice / Winbond style flash
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusRegister(pub u16);
+
+impl StatusRegister {
+    /// Write in progress
+    pub const WIP: u16 = 1 << 0;
+    /// Write enable latch
+    pub const WEL: u16 = 1 << 1;
+    /// Block protect BP0..BP2
+    pub const BP_MASK: u16 = 0b111 << 2;
+    /// Top/bottom protect
+    pub const TB: u16 = 1 << 5;
+    /// Sector (4K) granularity protect
+    pub const SEC: u16 = 1 << 6;
+    pub const SRP0: u16 = 1 << 7;
+    pub const SRP1: u16 = 1 << 8;
+    /// Quad enable
+    pub const QE: u16 = 1 << 9;
+    /// Complement protect
+    pub const CMP: u16 = 1 << 14;
+
+    /// All protection related bits
+    const PROTECT_MASK: u16 =
+        Self::BP_MASK | Self::TB | Self::SEC | Self::SRP0 | Self::SRP1 | Self::CMP;
+
+    /// Whether the flash with `jedec_id` has this layout and takes the
+    /// commands above. Macronix and ISSI keep QE in bit 6 of a single status
+    /// register and take 0x35 as enter QPI mode, other vendors are unchecked.
+    pub fn layout_known(jedec_id: &[u8]) -> bool {
+        matches!(
+            jedec_id.first().copied().and_then(Vendor::from_mid),
+            Some(Vendor::GigaDevice | Vendor::Winbond)
+        )
+    }
+
+    pub fn from_bytes(sr1: u8, sr2: u8) -> Self {
+        Self(u16::from_le_bytes([sr1, sr2]))
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.0 & Self::WIP != 0
+    }
+
+    pub fn block_protect(&self) -> u8 {
+        ((self.0 & Self::BP_MASK) >> 2) as u8
+    }
+
+    pub fn top_bottom(&self) -> bool {
+        self.0 & Self::TB != 0
+    }
+
+    pub fn sector(&self) -> bool {
+        self.0 & Self::SEC != 0
+    }
+
+    pub fn complement(&self) -> bool {
+        self.0 & Self::CMP != 0
+    }
+
+    pub fn quad_enable(&self) -> bool {
+        self.0 & Self::QE != 0
+    }
+
+    /// Whether any part of the array is write protected
+    pub fn is_protected(&self) -> bool {
+        match (self.complement(), self.block_protect()) {
+            (false, bp) => bp != 0,
+            (true, bp) => bp != 0b111,
+        }
+    }
+
+    /// Protected address range for a flash of `capacity` bytes.
+    ///
+    /// Follows the common GD25Q/W25Q table: BP1..6 protect capacity/64,
+    /// doubling per step (SEC=1: 4K doubling up to 32K), from the top or from
+    /// the bottom with TB=1. BP=7 protects everything, CMP=1 the complement.
+    pub fn protected_range(&self, capacity: u32) -> Range<u32> {
+        let bp = self.block_protect() as u32;
+        let size = match bp {
+            0 => 0,
+            7 => capacity,
+            _ if self.sector() => (4096 << (bp - 1)).min(32 * 1024),
+            _ => (capacity / 64) << (bp - 1),
+        }
+        .min(capacity);
+        let (start, end) = if self.top_bottom() || size == capacity {
+            (0, size)
+        } else {
+            (capacity - size, capacity)
+        };
+        if !self.complement() {
+            start..end
+        } else if start == 0 {
+            end..capacity
+        } else {
+            0..start
+        }
+    }
+
+    /// Same register with every protection bit cleared, QE kept
+    pub fn unprotected(self) -> Self {
+        Self(self.0 & !(Self::PROTECT_MASK | Self::WIP | Self::WEL))
+    }
+
+    /// Same register protecting the whole array
+    pub fn protected(self) -> Self {
+        Self(self.unprotected().0 | Self::BP_MASK)
+    }
+
+    pub fn with_quad_enable(self, enable: bool) -> Self {
+        let bits = self.0 & !(Self::WIP | Self::WEL);
+        if enable {
+            Self(bits | Self::QE)
+        } else {
+            Self(bits & !Self::QE)
+        }
+    }
+}
+
+impl fmt::Display for StatusRegister {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "0x{:04x} BP={} TB={} SEC={} CMP={} SRP={}/{} QE={}",
+            self.0,
+            self.block_protect(),
+            self.top_bottom() as u8,
+            self.sector() as u8,
+            self.complement() as u8,
+            (self.0 & Self::SRP0 != 0) as u8,
+            (self.0 & Self::SRP1 != 0) as u8,
+            self.quad_enable() as u8,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIB4: u32 = 4 * 1024 * 1024;
+
+    #[test]
+    fn layout_known() {
+        assert!(StatusRegister::layout_known(&[0xc8, 0x40, 0x17]));
+        assert!(StatusRegister::layout_known(&[0xef, 0x40, 0x17]));
+        assert!(!StatusRegister::layout_known(&[0xc2, 0x20, 0x16]));
+        assert!(!StatusRegister::layout_known(&[0x9d, 0x60, 0x16]));
+        assert!(!StatusRegister::layout_known(&[]));
+    }
+
+    #[test]
+    fn decode() {
+        let sr = StatusRegister::from_bytes(0x1c, 0x02);
+        assert_eq!(sr.block_protect(), 7);
+        assert!(sr.quad_enable());
+        assert!(sr.is_protected());
+        assert_eq!(sr.protected_range(MIB4), 0..MIB4);
+        assert_eq!(sr.to_string(), "0x021c BP=7 TB=0 SEC=0 CMP=0 SRP=0/0 QE=1");
+
+        let sr = StatusRegister::from_bytes(0x00, 0x02);
+        assert!(!sr.is_protected());
+        assert!(sr.protected_range(MIB4).is_empty());
+    }
+
+    #[test]
+    fn ranges() {
+        // upper 64K
+        let sr = StatusRegister(1 << 2);
+        assert_eq!(sr.protected_range(MIB4), MIB4 - 0x10000..MIB4);
+        // lower 1M
+        let sr = StatusRegister(5 << 2 | StatusRegister::TB);
+        assert_eq!(sr.protected_range(MIB4), 0..0x10_0000);
+        // top 8K
+        let sr = StatusRegister(2 << 2 | StatusRegister::SEC);
+        assert_eq!(sr.protected_range(MIB4), MIB4 - 0x2000..MIB4);
+        // everything but the upper 64K
+        let sr = StatusRegister(1 << 2 | StatusRegister::CMP);
+        assert_eq!(sr.protected_range(MIB4), 0..MIB4 - 0x10000);
+        assert!(sr.is_protected());
+        // CMP with BP=7 protects nothing
+        let sr = StatusRegister(7 << 2 | StatusRegister::CMP);
+        assert!(!sr.is_protected());
+        assert!(sr.protected_range(MIB4).is_empty());
+    }
+
+    #[test]
+    fn modify() {
+        let locked = StatusRegister(0x7f | StatusRegister::QE | StatusRegister::CMP);
+        let sr = locked.unprotected();
+        assert_eq!(sr, StatusRegister(StatusRegister::QE));
+        assert_eq!(sr.protected().block_protect(), 7);
+        assert!(!sr.protected().complement());
+        assert!(!sr.with_quad_enable(false).quad_enable());
+        assert!(StatusRegister(0).with_quad_enable(true).quad_enable());
+    }
+}
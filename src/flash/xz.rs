@@ -0,0 +1,52 @@
+use std::io::Write;
+
+use xz2::stream::{Check, Filters, LzmaOptions, Stream};
+use xz2::write::XzEncoder;
+
+use crate::error::{Error, Result};
+
+/// LZMA2 dictionary size the loader has room for
+pub const XZ_DICT_SIZE: u32 = 32 * 1024;
+
+/// Images smaller than this are written as is
+pub const COMPRESS_THRESHOLD: usize = 4096;
+
+/// Compress `data` to an xz stream the eflash loader can expand
+pub fn xz_compress(data: &[u8]) -> Result<Vec<u8>> {
+    let stream = LzmaOptions::new_preset(9)
+        .map(|mut options| {
+            options.dict_size(XZ_DICT_SIZE);
+            let mut filters = Filters::new();
+            filters.lzma2(&options);
+            filters
+        })
+        .and_then(|filters| Stream::new_stream_encoder(&filters, Check::Crc32))
+        .map_err(|e| Error::Custom(format!("xz: {}", e)))?;
+    let mut encoder = XzEncoder::new_stream(Vec::new(), stream);
+    encoder.write_all(data)?;
+    Ok(encoder.finish()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn round_trip() {
+        let mut data = vec![0u8; 64 * 1024];
+        data[..1000]
+            .iter_mut()
+            .enumerate()
+            .for_each(|(i, b)| *b = i as u8);
+        let compressed = xz_compress(&data).unwrap();
+        assert!(compressed.len() < 2048);
+        assert_eq!(&compressed[..6], b"\xfd7zXZ\x00");
+
+        let mut decoded = vec![];
+        xz2::read::XzDecoder::new(&compressed[..])
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, data);
+    }
+}
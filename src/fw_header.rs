@@ -1,28 +1,410 @@
 pub mod bl616;
 
-/// 256 byte efuse
-// BFNP
-/*
+use std::fmt;
 
- *(.text.entry)
-    KEEP (*(SORT_NONE(.init)))
-    KEEP (*(SORT_NONE(.vector)))
+use sha2::{Digest, Sha256};
+use thiserror::Error;
 
+use crate::image::{BOOT_HEADER_LEN, TEMPLATE};
+use crate::CRC32;
 
-__RFTLV_SIZE_OFFSET = 1K;
-__RFTLV_SIZE_HOLE = 2K;
-__RFTLV_HEAD1_H = (0x46524C42); /* BLRF */
-__RFTLV_HEAD1_L = (0x41524150); /* PAPA */
+// bootheader_t offsets
+const FLASH_CFG: usize = 0x08;
+const CLK_CFG: usize = 0x64;
+const CRC: usize = 0xfc;
+const FLASH_CFG_LEN: usize = bl616::FlashCfg::LEN;
+const CLK_CFG_LEN: usize = bl616::ClkCfg::LEN;
 
-. = ORIGIN(xip_memory) + __RFTLV_SIZE_OFFSET + __RFTLV_SIZE_HOLE;
+/// Flash as mapped for execute in place, where the image at
+/// `group_image_offset` shows up
+pub const XIP_BASE: u32 = 0xa000_0000;
 
-. __text_code_start__ = .;
+/// Part of a boot header carrying its own magic and crc32
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    /// The whole `bootheader_t`, `BFNP`
+    BootHeader,
+    /// `boot_flash_cfg_t`, `FCFG`
+    FlashConfig,
+    /// `boot_clk_cfg_t`, `PCFG`
+    ClockConfig,
+}
 
-    *(.text)
-    *(.text.*)
+impl Section {
+    pub fn magic(&self) -> &'static [u8; 4] {
+        match self {
+            Section::BootHeader => b"BFNP",
+            Section::FlashConfig => b"FCFG",
+            Section::ClockConfig => b"PCFG",
+        }
+    }
+}
 
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Section::BootHeader => "boot header",
+            Section::FlashConfig => "flash config",
+            Section::ClockConfig => "clock config",
+        };
+        f.write_str(s)
+    }
+}
 
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum HeaderError {
+    #[error("boot header too short: {0} bytes, expected 256")]
+    TooShort(usize),
+    #[error("{section} magic is {found:02x?}, expected {:?}", String::from_utf8_lossy(section.magic()))]
+    Magic { section: Section, found: [u8; 4] },
+    #[error("{section} crc32 mismatch: stored 0x{stored:08x}, computed 0x{computed:08x}")]
+    Crc {
+        section: Section,
+        stored: u32,
+        computed: u32,
+    },
+    #[error("image is {actual} bytes, header img_len_cnt is {expected}")]
+    ImageLen { expected: u32, actual: usize },
+    #[error("image sha256 mismatch: header {expected}, image {actual}")]
+    Hash { expected: String, actual: String },
+}
 
- */
+/// BL616 boot header, as found at the start of flash and in `bootinfo.bin`
+#[derive(Debug, Clone, Copy)]
+pub struct FwHeader(bl616::BootHeader);
 
-pub struct FwHeader(bl616::bootheader_t);
+impl FwHeader {
+    /// Parse and validate the first 256 bytes of `raw`.
+    ///
+    /// Checks the magics and the crc32 of the flash and clock configs, and
+    /// the header crc32 unless `crc_ignore` is set.
+    pub fn parse(raw: &[u8]) -> Result<Self, HeaderError> {
+        if raw.len() < BOOT_HEADER_LEN {
+            return Err(HeaderError::TooShort(raw.len()));
+        }
+        let raw = &raw[..BOOT_HEADER_LEN];
+        check_magic(Section::BootHeader, raw)?;
+        check_section(
+            Section::FlashConfig,
+            &raw[FLASH_CFG..FLASH_CFG + FLASH_CFG_LEN],
+        )?;
+        check_section(Section::ClockConfig, &raw[CLK_CFG..CLK_CFG + CLK_CFG_LEN])?;
+
+        let header = Self(bl616::BootHeader::from_bytes(raw.try_into().unwrap()));
+        if !header.crc_ignore() {
+            check_crc(Section::BootHeader, &raw[..CRC], read_u32(raw, CRC))?;
+        }
+        Ok(header)
+    }
+
+    /// Check `image`, the firmware following the header, against
+    /// `img_len_cnt` and the SHA-256 in `basic_cfg.hash`
+    pub fn verify_image(&self, image: &[u8]) -> Result<(), HeaderError> {
+        if self.hash_ignore() {
+            return Ok(());
+        }
+        let image = if self.no_segment() {
+            let len = self.img_len_cnt();
+            image.get(..len as usize).ok_or(HeaderError::ImageLen {
+                expected: len,
+                actual: image.len(),
+            })?
+        } else {
+            image
+        };
+        let actual = Sha256::digest(image);
+        if actual[..] != self.hash() {
+            return Err(HeaderError::Hash {
+                expected: hex::encode(self.hash()),
+                actual: hex::encode(actual),
+            });
+        }
+        Ok(())
+    }
+
+    pub fn as_raw(&self) -> &bl616::BootHeader {
+        &self.0
+    }
+
+    pub fn to_bytes(&self) -> [u8; BOOT_HEADER_LEN] {
+        self.0.to_bytes()
+    }
+
+    /// Image is one raw binary of `img_len_cnt` bytes, not segments
+    pub fn no_segment(&self) -> bool {
+        self.0.basic_cfg.no_segment() != 0
+    }
+
+    pub fn crc_ignore(&self) -> bool {
+        self.0.basic_cfg.crc_ignore() != 0
+    }
+
+    pub fn hash_ignore(&self) -> bool {
+        self.0.basic_cfg.hash_ignore() != 0
+    }
+
+    /// Image length in bytes, or segment count
+    pub fn img_len_cnt(&self) -> u32 {
+        self.0.basic_cfg.img_len_cnt
+    }
+
+    /// Flash offset of the image
+    pub fn group_image_offset(&self) -> u32 {
+        self.0.basic_cfg.group_image_offset
+    }
+
+    /// SHA-256 of the image
+    pub fn hash(&self) -> [u8; 32] {
+        self.0.basic_cfg.hash
+    }
+}
+
+/// Builds the boot header for a raw firmware binary run from flash, what the
+/// vendor `img_create_mcu` puts in `bootinfo.bin`.
+///
+/// Flash and clock config default to those of the bundled `bootinfo.bin`.
+#[derive(Debug, Clone)]
+pub struct FwHeaderBuilder<'a> {
+    firmware: &'a [u8],
+    flash_cfg: bl616::SpiFlashCfg,
+    clk_cfg: bl616::SysClkCfg,
+    group_image_offset: u32,
+    entry: u32,
+}
+
+impl<'a> FwHeaderBuilder<'a> {
+    pub fn new(firmware: &'a [u8]) -> Self {
+        let template = template();
+        Self {
+            firmware,
+            flash_cfg: template.flash_cfg.cfg,
+            clk_cfg: template.clk_cfg.cfg,
+            group_image_offset: 0x2000,
+            entry: XIP_BASE,
+        }
+    }
+
+    pub fn flash_cfg(mut self, cfg: bl616::SpiFlashCfg) -> Self {
+        self.flash_cfg = cfg;
+        self
+    }
+
+    pub fn clk_cfg(mut self, cfg: bl616::SysClkCfg) -> Self {
+        self.clk_cfg = cfg;
+        self
+    }
+
+    /// Flash offset the firmware is written to, 0x2000 by default
+    pub fn group_image_offset(mut self, offset: u32) -> Self {
+        self.group_image_offset = offset;
+        self
+    }
+
+    /// Boot CPU entry point, the start of the image in XIP by default
+    pub fn entry(mut self, entry: u32) -> Self {
+        self.entry = entry;
+        self
+    }
+
+    pub fn build(&self) -> FwHeader {
+        let mut header = template();
+        header.flash_cfg.cfg = self.flash_cfg;
+        header.clk_cfg.cfg = self.clk_cfg;
+
+        let basic_cfg = &mut header.basic_cfg;
+        basic_cfg.set_sign_type(0);
+        basic_cfg.set_encrypt_type(0);
+        basic_cfg.set_no_segment(1);
+        basic_cfg.set_crc_ignore(0);
+        basic_cfg.set_hash_ignore(0);
+        basic_cfg.group_image_offset = self.group_image_offset;
+        basic_cfg.aes_region_len = 0;
+        basic_cfg.img_len_cnt = self.firmware.len() as u32;
+        basic_cfg.hash = Sha256::digest(self.firmware).into();
+
+        let cpu_cfg = &mut header.cpu_cfg;
+        cpu_cfg.config_enable = 1;
+        cpu_cfg.halt_cpu = 0;
+        cpu_cfg.image_address_offset = 0;
+        cpu_cfg.boot_entry = self.entry;
+
+        // no flash config table follows the header
+        header.flash_cfg_table_addr = 0;
+        header.flash_cfg_table_len = 0;
+
+        header.flash_cfg.crc32 = CRC32.checksum(&header.flash_cfg.cfg.to_bytes());
+        header.clk_cfg.crc32 = CRC32.checksum(&header.clk_cfg.cfg.to_bytes());
+        header.crc32 = CRC32.checksum(&header.to_bytes()[..CRC]);
+        FwHeader(header)
+    }
+}
+
+/// Boot header of the bundled `bootinfo.bin`
+fn template() -> bl616::BootHeader {
+    FwHeader::parse(TEMPLATE)
+        .expect("bundled boot header is valid")
+        .0
+}
+
+fn read_u32(raw: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(raw[offset..offset + 4].try_into().unwrap())
+}
+
+fn check_magic(section: Section, raw: &[u8]) -> Result<(), HeaderError> {
+    let found: [u8; 4] = raw[..4].try_into().unwrap();
+    if &found != section.magic() {
+        return Err(HeaderError::Magic { section, found });
+    }
+    Ok(())
+}
+
+fn check_crc(section: Section, data: &[u8], stored: u32) -> Result<(), HeaderError> {
+    let computed = CRC32.checksum(data);
+    if computed != stored {
+        return Err(HeaderError::Crc {
+            section,
+            stored,
+            computed,
+        });
+    }
+    Ok(())
+}
+
+/// magic, cfg, crc32 over cfg
+fn check_section(section: Section, raw: &[u8]) -> Result<(), HeaderError> {
+    check_magic(section, raw)?;
+    let crc = raw.len() - 4;
+    check_crc(section, &raw[4..crc], read_u32(raw, crc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOOTINFO: &[u8] = include_bytes!("../chips/bootinfo.bin");
+    const HASH: usize = 0x88;
+
+    #[test]
+    fn parse_bootinfo() {
+        let header = FwHeader::parse(BOOTINFO).unwrap();
+        assert!(header.no_segment());
+        assert!(!header.hash_ignore());
+        assert_eq!(header.img_len_cnt(), 0x6d40);
+        assert_eq!(header.group_image_offset(), 0x2000);
+        assert_eq!(&header.hash()[..4], &[0xcd, 0x6f, 0xfb, 0xf4]);
+        assert_eq!(header.to_bytes()[..], BOOTINFO[..256]);
+
+        assert_eq!(
+            header.verify_image(&[0; 16]),
+            Err(HeaderError::ImageLen {
+                expected: 0x6d40,
+                actual: 16
+            })
+        );
+        assert!(matches!(
+            header.verify_image(&[0; 0x6d40]),
+            Err(HeaderError::Hash { .. })
+        ));
+    }
+
+    #[test]
+    fn reject_corrupted() {
+        assert_eq!(
+            FwHeader::parse(&BOOTINFO[..100]).unwrap_err(),
+            HeaderError::TooShort(100)
+        );
+
+        let mut raw = BOOTINFO[..256].to_vec();
+        raw[0x64] = b'X';
+        let err = FwHeader::parse(&raw).unwrap_err();
+        assert!(matches!(
+            err,
+            HeaderError::Magic {
+                section: Section::ClockConfig,
+                ..
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "clock config magic is [58, 43, 46, 47], expected \"PCFG\""
+        );
+
+        let mut raw = BOOTINFO[..256].to_vec();
+        raw[0x10] ^= 1;
+        assert!(matches!(
+            FwHeader::parse(&raw),
+            Err(HeaderError::Crc {
+                section: Section::FlashConfig,
+                ..
+            })
+        ));
+
+        let mut raw = BOOTINFO[..256].to_vec();
+        raw[0x84] ^= 1;
+        assert_eq!(
+            FwHeader::parse(&raw).unwrap_err(),
+            HeaderError::Crc {
+                section: Section::BootHeader,
+                stored: 0x6bef8cfc,
+                computed: CRC32.checksum(&raw[..CRC]),
+            }
+        );
+    }
+
+    #[test]
+    fn verify_image() {
+        let image = vec![0x5a; 1000];
+        let mut raw = BOOTINFO[..256].to_vec();
+        raw[0x84..0x88].copy_from_slice(&1000u32.to_le_bytes());
+        raw[HASH..HASH + 32].copy_from_slice(&Sha256::digest(&image));
+        let crc = CRC32.checksum(&raw[..CRC]);
+        raw[CRC..].copy_from_slice(&crc.to_le_bytes());
+
+        let header = FwHeader::parse(&raw).unwrap();
+        header.verify_image(&image).unwrap();
+        // trailing padding is not hashed
+        let mut padded = image.clone();
+        padded.resize(1024, 0xff);
+        header.verify_image(&padded).unwrap();
+    }
+
+    #[test]
+    fn build() {
+        let firmware: Vec<u8> = (0..0x6d40).map(|i| i as u8).collect();
+        let header = FwHeaderBuilder::new(&firmware).build();
+        header.verify_image(&firmware).unwrap();
+        assert_eq!(header.img_len_cnt(), 0x6d40);
+        assert_eq!(header.group_image_offset(), 0x2000);
+
+        // same as img_create_mcu, but for the hash and the flash config table
+        let raw = header.to_bytes();
+        assert_eq!(raw[..HASH], BOOTINFO[..HASH]);
+        assert_eq!(raw[0xa8..0xc0], BOOTINFO[0xa8..0xc0]);
+        assert_eq!(raw[0xc0..0xc8], [0; 8]);
+
+        let mut clk_cfg = header.as_raw().clk_cfg.cfg;
+        clk_cfg.flash_clk_div = 3;
+        let header = FwHeaderBuilder::new(&firmware[..100])
+            .clk_cfg(clk_cfg)
+            .group_image_offset(0x10000)
+            .entry(XIP_BASE + 0x1000)
+            .build();
+        let raw = header.to_bytes();
+        assert_eq!(raw[CLK_CFG + 4 + 8], 3);
+        assert_ne!(
+            raw[CLK_CFG..CLK_CFG + CLK_CFG_LEN],
+            BOOTINFO[CLK_CFG..CLK_CFG + CLK_CFG_LEN]
+        );
+        assert_eq!(header.group_image_offset(), 0x10000);
+        assert_eq!(read_u32(&raw, 0xb0), 0xa000_1000);
+        header.verify_image(&firmware[..100]).unwrap();
+        assert!(header.verify_image(&firmware[1..]).is_err());
+
+        // second source flash, W25Q64JV
+        let flash_cfg = crate::flash::flash_config(&[0xef, 0x40, 0x17]);
+        let header = FwHeaderBuilder::new(&firmware).flash_cfg(flash_cfg).build();
+        let header = FwHeader::parse(&header.to_bytes()).unwrap();
+        assert_eq!(header.as_raw().flash_cfg.cfg, flash_cfg);
+        assert_eq!(header.as_raw().flash_cfg.cfg.mid, 0xef);
+    }
+}
@@ -0,0 +1,267 @@
+//! Segmented images for the BootROM RAM loader.
+//!
+//! A RAM image is a boot header followed by segments, each a 16 byte
+//! segment header and its data. The boot header carries the segment count,
+//! the SHA-256 of all segments (headers included) and the entry point.
+
+use std::ops::Range;
+
+use sha2::{Digest, Sha256};
+
+use crate::error::{Error, Result};
+use crate::CRC32;
+
+pub const BOOT_HEADER_LEN: usize = 256;
+pub const SEGMENT_HEADER_LEN: usize = 16;
+
+/// Largest `LoadSegData` payload the ROM accepts
+pub const SEGMENT_CHUNK: usize = 4096;
+
+/// BL616 on-chip RAM, as laid out in `demo/memory.x`
+pub const ITCM_OCRAM: Range<u32> = 0x62fc_0000..0x62fc_5000;
+pub const DTCM_OCRAM: Range<u32> = 0x62fc_5000..0x62fc_6000;
+pub const OCRAM: Range<u32> = 0x62fc_6000..0x6301_0000;
+
+/// Boot header of `chips/bootinfo.bin`, for its flash and clock config
+pub(crate) const TEMPLATE: &[u8] = include_bytes!("../chips/bootinfo.bin");
+
+// bootheader_t offsets
+const BASIC_CFG: usize = 0x78;
+const GROUP_IMAGE_OFFSET: usize = 0x7c;
+const AES_REGION_LEN: usize = 0x80;
+const IMG_LEN_CNT: usize = 0x84;
+const HASH: usize = 0x88;
+const IMAGE_ADDRESS_OFFSET: usize = 0xac;
+const BOOT_ENTRY: usize = 0xb0;
+const CRC: usize = 0xfc;
+
+// basic_cfg bits
+const NO_SEGMENT: u32 = 1 << 8;
+const CRC_IGNORE: u32 = 1 << 16;
+const HASH_IGNORE: u32 = 1 << 17;
+
+#[derive(Debug, Clone)]
+pub struct Segment {
+    /// Load address
+    pub addr: u32,
+    pub data: Vec<u8>,
+}
+
+impl Segment {
+    pub fn header(&self) -> [u8; SEGMENT_HEADER_LEN] {
+        let mut raw = [0u8; SEGMENT_HEADER_LEN];
+        raw[0..4].copy_from_slice(&self.addr.to_le_bytes());
+        raw[4..8].copy_from_slice(&(self.data.len() as u32).to_le_bytes());
+        let crc = CRC32.checksum(&raw[..12]);
+        raw[12..16].copy_from_slice(&crc.to_le_bytes());
+        raw
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RamImage {
+    pub boot_header: Vec<u8>,
+    pub segments: Vec<Segment>,
+}
+
+impl RamImage {
+    /// Image starting at `entry`, boot header derived from the bundled template
+    pub fn new(entry: u32, segments: Vec<Segment>) -> Self {
+        Self::with_boot_header(&TEMPLATE[..BOOT_HEADER_LEN], entry, segments)
+            .expect("bundled boot header is valid")
+    }
+
+    /// Image with a custom boot header, segment count, hash, entry and crc
+    /// are filled in
+    pub fn with_boot_header(template: &[u8], entry: u32, segments: Vec<Segment>) -> Result<Self> {
+        if template.len() < BOOT_HEADER_LEN || &template[..4] != b"BFNP" {
+            return Err(Error::Custom(
+                "boot header template: not a BFNP boot header".to_string(),
+            ));
+        }
+        let mut header = template[..BOOT_HEADER_LEN].to_vec();
+
+        let mut basic_cfg = read_u32(&header, BASIC_CFG);
+        basic_cfg &= !(NO_SEGMENT | CRC_IGNORE | HASH_IGNORE);
+        write_u32(&mut header, BASIC_CFG, basic_cfg);
+        write_u32(&mut header, GROUP_IMAGE_OFFSET, 0);
+        write_u32(&mut header, AES_REGION_LEN, 0);
+        write_u32(&mut header, IMG_LEN_CNT, segments.len() as u32);
+
+        let mut hasher = Sha256::new();
+        for segment in &segments {
+            hasher.update(segment.header());
+            hasher.update(&segment.data);
+        }
+        header[HASH..HASH + 32].copy_from_slice(&hasher.finalize());
+
+        write_u32(&mut header, IMAGE_ADDRESS_OFFSET, 0);
+        write_u32(&mut header, BOOT_ENTRY, entry);
+        let crc = CRC32.checksum(&header[..CRC]);
+        write_u32(&mut header, CRC, crc);
+
+        Ok(Self {
+            boot_header: header,
+            segments,
+        })
+    }
+
+    /// Raw binary loaded at and started from `addr`, which must fit in RAM
+    pub fn from_binary(addr: u32, mut data: Vec<u8>) -> Result<Self> {
+        if !data.len().is_multiple_of(16) {
+            data.resize(data.len() + 16 - data.len() % 16, 0);
+        }
+        let end = addr as u64 + data.len() as u64;
+        if addr < ITCM_OCRAM.start || end > OCRAM.end as u64 {
+            return Err(Error::Custom(format!(
+                "image 0x{:08x}..0x{:08x} does not fit in RAM 0x{:08x}..0x{:08x}",
+                addr, end, ITCM_OCRAM.start, OCRAM.end
+            )));
+        }
+        Ok(Self::new(addr, vec![Segment { addr, data }]))
+    }
+
+    /// Parse an image file as shipped by the vendor tools (e.g. the eflash
+    /// loader): boot header followed by `img_len_cnt` segments
+    pub fn parse(raw: &[u8]) -> Result<Self> {
+        if raw.len() < BOOT_HEADER_LEN || &raw[..4] != b"BFNP" {
+            return Err(Error::Custom("image: not a BFNP boot header".to_string()));
+        }
+        let boot_header = raw[..BOOT_HEADER_LEN].to_vec();
+        let count = read_u32(&boot_header, IMG_LEN_CNT);
+        let mut segments = vec![];
+        let mut rest = &raw[BOOT_HEADER_LEN..];
+        for i in 0..count {
+            if rest.len() < SEGMENT_HEADER_LEN {
+                return Err(Error::Custom(format!("image: segment {} truncated", i)));
+            }
+            let addr = read_u32(rest, 0);
+            let len = read_u32(rest, 4) as usize;
+            if CRC32.checksum(&rest[..12]) != read_u32(rest, 12) {
+                return Err(Error::Custom(format!("image: segment {} header crc", i)));
+            }
+            rest = &rest[SEGMENT_HEADER_LEN..];
+            if rest.len() < len {
+                return Err(Error::Custom(format!("image: segment {} truncated", i)));
+            }
+            segments.push(Segment {
+                addr,
+                data: rest[..len].to_vec(),
+            });
+            rest = &rest[len..];
+        }
+        Ok(Self {
+            boot_header,
+            segments,
+        })
+    }
+
+    /// Image file layout, as accepted by [`RamImage::parse`]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut raw = self.boot_header.clone();
+        for segment in &self.segments {
+            raw.extend_from_slice(&segment.header());
+            raw.extend_from_slice(&segment.data);
+        }
+        raw
+    }
+
+    pub fn entry(&self) -> u32 {
+        read_u32(&self.boot_header, BOOT_ENTRY)
+    }
+}
+
+fn read_u32(raw: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(raw[offset..offset + 4].try_into().unwrap())
+}
+
+fn write_u32(raw: &mut [u8], offset: usize, val: u32) {
+    raw[offset..offset + 4].copy_from_slice(&val.to_le_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::{Session, SyncOptions};
+    use crate::sim::{Simulator, RAM_BASE};
+
+    #[test]
+    fn boot_header() {
+        let image = RamImage::from_binary(ITCM_OCRAM.start, vec![0x13; 100]).unwrap();
+        let header = &image.boot_header;
+        assert_eq!(header.len(), BOOT_HEADER_LEN);
+        assert_eq!(read_u32(header, IMG_LEN_CNT), 1);
+        assert_eq!(read_u32(header, BASIC_CFG) & NO_SEGMENT, 0);
+        assert_eq!(image.entry(), 0x62fc_0000);
+        assert_eq!(read_u32(header, CRC), CRC32.checksum(&header[..CRC]));
+        // padded
+        assert_eq!(image.segments[0].data.len(), 112);
+
+        let seg = image.segments[0].header();
+        assert_eq!(seg[..8], [0x00, 0x00, 0xfc, 0x62, 112, 0, 0, 0]);
+
+        assert!(RamImage::from_binary(OCRAM.end - 16, vec![0; 32]).is_err());
+        assert!(RamImage::from_binary(0x2000_0000, vec![0; 32]).is_err());
+    }
+
+    #[test]
+    fn parse_image_file() {
+        let image = RamImage::new(
+            0x62fc_0000,
+            vec![
+                Segment {
+                    addr: 0x62fc_0000,
+                    data: vec![1; 32],
+                },
+                Segment {
+                    addr: 0x62fc_6000,
+                    data: vec![2; 48],
+                },
+            ],
+        );
+        let raw = image.to_bytes();
+        assert_eq!(raw.len(), 256 + 16 + 32 + 16 + 48);
+        let parsed = RamImage::parse(&raw).unwrap();
+        assert_eq!(parsed.boot_header, image.boot_header);
+        assert_eq!(parsed.segments.len(), 2);
+        assert_eq!(parsed.segments[1].addr, 0x62fc_6000);
+        assert_eq!(parsed.segments[1].data, vec![2; 48]);
+
+        assert!(RamImage::parse(&raw[..raw.len() - 1]).is_err());
+        assert!(RamImage::parse(&raw[4..]).is_err());
+    }
+
+    #[test]
+    fn load_and_run() {
+        let firmware: Vec<u8> = (0..10_000).map(|i| i as u8).collect();
+        let image = RamImage::from_binary(ITCM_OCRAM.start, firmware.clone()).unwrap();
+
+        let mut session = Session::connect(Simulator::new(), &SyncOptions::default()).unwrap();
+        session.run_image(&image).unwrap();
+        let sim = session.into_inner();
+        assert_eq!(sim.entry, Some(0x62fc_0000));
+        let offset = (ITCM_OCRAM.start - RAM_BASE) as usize;
+        assert_eq!(sim.ram[offset..offset + firmware.len()], firmware[..]);
+        assert!(!sim.is_synced());
+
+        // no flash loader, the firmware runs and the ROM is gone
+        let mut sim = Simulator::new();
+        sim.emulate_flash_loader = false;
+        let mut session = Session::connect(sim, &SyncOptions::default()).unwrap();
+        session.run_image(&image).unwrap();
+        let mut sim = session.into_inner();
+        assert!(crate::session::sync(&mut sim, &SyncOptions::default()).is_err());
+    }
+
+    #[test]
+    fn corrupted_image() {
+        let mut image = RamImage::from_binary(ITCM_OCRAM.start, vec![0x13; 64]).unwrap();
+        // hash no longer matches
+        image.segments[0].data[0] = 0;
+
+        let mut session = Session::connect(Simulator::new(), &SyncOptions::default()).unwrap();
+        let err = session.run_image(&image).unwrap_err();
+        assert_eq!(err.code(), Some(crate::error::ErrorCode::ImgHash));
+        assert_eq!(session.transport().entry, None);
+    }
+}
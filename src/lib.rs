@@ -1,7 +1,13 @@
 use crc::{Crc, CRC_32_ISO_HDLC};
 
+pub mod bootlog;
 pub mod commands;
+pub mod efuse;
 pub mod error;
+pub mod flash;
+pub mod image;
+pub mod session;
+pub mod sim;
 pub mod transport;
 
 pub mod fw_header;
@@ -1,60 +1,491 @@
-use std::{env, os, time::Duration};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use bl::{
-    commands::{self, Command},
-    transport::Transport,
+    bootlog::BootLog,
+    commands, flash,
+    fw_header::{bl616::SpiFlashCfg, FwHeader, FwHeaderBuilder},
+    image::RamImage,
+    session::{Session, ShaMode, SyncOptions},
+    transport::{Recorder, Replay, SerialControl, TcpTransport, Transport},
 };
-use crc::{Crc, CRC_32_ISO_HDLC};
-use serialport::SerialPort;
+use clap::{ArgAction, Parser, Subcommand, ValueEnum};
+use log::LevelFilter;
+
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    /// Serial port, e.g. /dev/tty.usbserial-0001, rfc2217://host:port or tcp://host:port
+    #[arg(short, long)]
+    port: Option<String>,
+    /// UART speed to switch to after sync, slower rates are tried when it fails
+    #[arg(short, long, default_value_t = 2_000_000)]
+    baud: u32,
+    /// Eflash loader image to run from RAM and send commands to
+    #[arg(long, value_name = "FILE")]
+    flash_loader: Option<PathBuf>,
+    /// Capture all traffic with the device to a file
+    #[arg(long, value_name = "FILE")]
+    record: Option<PathBuf>,
+    /// Serve a capture made with --record instead of talking to a device
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["port", "record"])]
+    replay: Option<PathBuf>,
+    /// More log output on stderr, -vv for commands, -vvv for raw frames
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+    /// Only log errors
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Write firmware to flash, or manage flash write protection
+    #[command(args_conflicts_with_subcommands = true)]
+    Flash {
+        #[arg(required = true)]
+        firmware: Option<PathBuf>,
+        /// How the device hashes flash to verify what was written
+        #[arg(long, value_enum, default_value_t = Verify::Xip)]
+        verify: Verify,
+        /// Boot header (bootinfo.bin) to write at 0 instead of one built for the
+        /// firmware, checked against the firmware first
+        #[arg(long, value_name = "FILE")]
+        boot_header: Option<PathBuf>,
+        /// Vendor flash .conf for this part, sent to the device and written to
+        /// the built boot header
+        #[arg(long, value_name = "FILE", conflicts_with = "boot_header")]
+        flash_conf: Option<PathBuf>,
+        #[command(subcommand)]
+        command: Option<FlashCommands>,
+    },
+    /// Load firmware into RAM and start it, flash is left alone
+    Run {
+        firmware: PathBuf,
+        /// Load and entry address, ITCM by default
+        #[arg(long, default_value = "0x62fc0000", value_parser = parse_u32)]
+        addr: u32,
+        /// Boot header template instead of the bundled one
+        #[arg(long, value_name = "FILE")]
+        boot_header: Option<PathBuf>,
+    },
+    /// Erase a flash range, or the whole chip
+    Erase {
+        /// Erase the whole chip, this can take minutes
+        #[arg(long, conflicts_with_all = ["addr", "len"])]
+        all: bool,
+        #[arg(required_unless_present = "all", value_parser = parse_u32)]
+        addr: Option<u32>,
+        #[arg(required_unless_present = "all", value_parser = parse_u32)]
+        len: Option<u32>,
+    },
+    /// Dump flash to a file, checked against the SHA-256 computed on device
+    Read {
+        /// Dump the whole flash to FILE, size taken from the JEDEC id
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["addr", "len", "output"])]
+        all: Option<PathBuf>,
+        #[arg(required_unless_present = "all", value_parser = parse_u32)]
+        addr: Option<u32>,
+        #[arg(required_unless_present = "all", value_parser = parse_u32)]
+        len: Option<u32>,
+        #[arg(required_unless_present = "all")]
+        output: Option<PathBuf>,
+    },
+    /// Show the BootROM log, decoded
+    Log {
+        /// Print as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Convert a vendor flash .conf to a flash_para.bin blob, no device needed
+    FlashConf { conf: PathBuf, output: PathBuf },
+    /// Access memory and registers
+    Mem {
+        #[command(subcommand)]
+        command: MemCommands,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Verify {
+    /// Read back through the XIP cache
+    Xip,
+    /// Read back through the SPI flash controller
+    Flash,
+    /// Skip verification
+    None,
+}
+
+#[derive(Subcommand)]
+enum FlashCommands {
+    /// Show the status registers and the write protected range
+    Status,
+    /// Write protect the whole flash
+    Protect,
+    /// Clear all block protection bits
+    Unprotect,
+}
+
+#[derive(Subcommand)]
+enum MemCommands {
+    /// Read 32-bit words
+    Read {
+        #[arg(value_parser = parse_u32)]
+        addr: u32,
+        /// Number of words
+        #[arg(default_value_t = 1, value_parser = parse_u32)]
+        count: u32,
+    },
+    /// Write a 32-bit word
+    Write {
+        #[arg(value_parser = parse_u32)]
+        addr: u32,
+        #[arg(value_parser = parse_u32)]
+        value: u32,
+    },
+}
+
+/// Decimal or 0x-prefixed hex
+fn parse_u32(s: &str) -> std::result::Result<u32, String> {
+    let ret = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(&hex.replace('_', ""), 16),
+        None => s.replace('_', "").parse(),
+    };
+    ret.map_err(|e| format!("{}: {}", s, e))
+}
+
+/// Last address of `len` bytes at `addr`, as `FlashErase` takes it
+fn range_end(addr: u32, len: usize) -> Result<u32> {
+    u32::try_from(len)
+        .ok()
+        .filter(|&len| len > 0)
+        .and_then(|len| addr.checked_add(len - 1))
+        .ok_or_else(|| anyhow::anyhow!("bad flash range 0x{:x} + 0x{:x}", addr, len))
+}
 
 fn main() -> Result<()> {
-    let dev = env::args()
-        .nth(1)
-        .expect("usage: prog /dev/tty.usbserial-0001 firmware.bin");
-    let fname = env::args()
-        .nth(2)
-        .expect("usage: prog /dev/tty.usbserial-0001 firmware.bin");
+    let cli = Cli::parse();
 
-    let mut firmware = std::fs::read(fname)?;
-    if firmware.len() % 16 != 0 {
-        firmware.resize(firmware.len() + 16 - firmware.len() % 16, 0);
+    let level = match (cli.quiet, cli.verbose) {
+        (true, _) => LevelFilter::Error,
+        (_, 0) => LevelFilter::Warn,
+        (_, 1) => LevelFilter::Info,
+        (_, 2) => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    };
+    // RUST_LOG still takes precedence
+    env_logger::Builder::new()
+        .filter_level(level)
+        .parse_default_env()
+        .init();
+    let options = SyncOptions::default();
+
+    if let Commands::FlashConf { conf, output } = &cli.command {
+        return flash_conf(conf, output);
+    }
+    if let Some(path) = &cli.replay {
+        return run(Session::connect(Replay::open(path)?, &options)?, cli);
+    }
+    let Some(port) = cli.port.clone() else {
+        anyhow::bail!("--port or --replay is required");
+    };
+    if let Some(addr) = port.strip_prefix("rfc2217://") {
+        connect(TcpTransport::connect_rfc2217(addr)?, &options, cli)
+    } else if let Some(addr) = port.strip_prefix("tcp://") {
+        connect(TcpTransport::connect_raw(addr)?, &options, cli)
+    } else {
+        let port = serialport::new(&port, options.baud_rate)
+            .timeout(options.timeout)
+            .open()?;
+        connect(port, &options, cli)
     }
+}
 
-    println!("Firmware size: {}", firmware.len());
+fn connect<T: Transport + SerialControl>(
+    transport: T,
+    options: &SyncOptions,
+    cli: Cli,
+) -> Result<()> {
+    match &cli.record {
+        Some(path) => run(
+            Session::connect(Recorder::create(transport, path)?, options)?,
+            cli,
+        ),
+        None => run(Session::connect(transport, options)?, cli),
+    }
+}
+
+fn run<T: Transport + SerialControl>(mut serial: Session<T>, cli: Cli) -> Result<()> {
+    // stdout is left to the command, `bl log --json` is parsed by scripts
+    log::info!("boot info => {:?}", serial.boot_info());
+    log::info!("chip id {:?}", serial.chip_id());
 
-    let mut serial = serialport::new(&dev, 115200)
-        .open()
-        .expect("failed to open port");
-    serial.set_timeout(Duration::from_secs(10)).unwrap();
+    // Clock PLL set. clk_set
+    let baud_rate = serial.switch_baud_rate(cli.baud)?;
+    log::info!("baud rate => {}", baud_rate);
 
-    let mut buf = [0u8; 256];
+    if let Some(path) = &cli.flash_loader {
+        let loader = RamImage::parse(&std::fs::read(path)?)?;
+        serial.start_flash_loader(&loader)?;
+        log::info!("flash loader => {}", path.display());
+    }
 
-    // send uart sync bytes..
-    let sync_len = (0.006 * 115200.0 / 10.0) as usize;
-    println!("sync_len: {}", sync_len);
-    let sync_bytes = vec![0x55_u8; sync_len];
+    match cli.command {
+        Commands::Flash {
+            command: Some(command),
+            ..
+        } => flash_protection(&mut serial, command),
+        Commands::Flash {
+            firmware,
+            verify,
+            boot_header,
+            flash_conf,
+            ..
+        } => flash(
+            &mut serial,
+            &firmware.unwrap(),
+            boot_header.as_deref(),
+            flash_conf.as_deref(),
+            verify,
+        ),
+        Commands::Run {
+            firmware,
+            addr,
+            boot_header,
+        } => run_ram(&mut serial, &firmware, addr, boot_header.as_deref()),
+        Commands::Erase { all, addr, len } => {
+            erase(&mut serial, (!all).then(|| (addr.unwrap(), len.unwrap())))
+        }
+        Commands::Read {
+            all: Some(output), ..
+        } => read(&mut serial, None, &output),
+        Commands::Read {
+            addr, len, output, ..
+        } => read(
+            &mut serial,
+            Some((addr.unwrap(), len.unwrap())),
+            &output.unwrap(),
+        ),
+        Commands::Log { json } => log(&mut serial, json),
+        Commands::Mem { command } => mem(&mut serial, command),
+        Commands::FlashConf { conf, output } => flash_conf(&conf, &output),
+    }
+}
 
-    serial.write(&sync_bytes).unwrap();
-    let n = serial.read(&mut buf[..]).unwrap();
+fn run_ram<T: Transport>(
+    serial: &mut Session<T>,
+    fname: &Path,
+    addr: u32,
+    boot_header: Option<&Path>,
+) -> Result<()> {
+    let firmware = std::fs::read(fname)?;
+    let mut image = RamImage::from_binary(addr, firmware)?;
+    if let Some(path) = boot_header {
+        image = RamImage::with_boot_header(&std::fs::read(path)?, addr, image.segments)?;
+    }
+    println!(
+        "Loading {} bytes to 0x{:08x}",
+        image.segments[0].data.len(),
+        addr
+    );
+    serial.run_image(&image)?;
+    println!("Running at 0x{:08x}", image.entry());
+    Ok(())
+}
 
-    println!("sync <= {:?}", &buf[..n]);
-    let payload = &buf[..n];
-    if payload.ends_with(b"OK") {
-        println!("baudrate sync OK");
+/// Fail if the flash is write protected, skipped for flash whose status
+/// register layout isn't known
+fn check_unprotected<T: Transport>(serial: &mut Session<T>) -> Result<()> {
+    if !serial.has_known_status_layout()? {
+        log::warn!("unknown flash status register layout, not checking write protection");
+        return Ok(());
+    }
+    let status = serial.read_status()?;
+    if status.is_protected() {
+        anyhow::bail!(
+            "flash is write protected ({}), run `bl flash unprotect` first",
+            status
+        );
     }
+    Ok(())
+}
 
-    let boot_info = serial.send_command(commands::GetBootInfo)?;
-    println!("boot info => {:?}", boot_info);
-    // get_boot_info
+/// Erase `addr, len`, or the whole chip if `None`
+fn erase<T: Transport>(serial: &mut Session<T>, range: Option<(u32, u32)>) -> Result<()> {
+    serial.set_flash_para()?;
+    check_unprotected(serial)?;
 
-    // 43484950574230334130305f424c0000
-    //"CHIPWB03A00_BL\x00\x00"
-    let chip_id = serial.send_command(commands::GetChipId)?;
-    println!("chip id {:?}", chip_id);
+    let start = Instant::now();
+    let mut progress = |elapsed: Duration| {
+        print!("\rErasing... {:.1}s", elapsed.as_secs_f32());
+        let _ = io::stdout().flush();
+    };
+    match range {
+        None => {
+            println!("Erasing whole chip");
+            serial.erase_chip(&mut progress)?;
+        }
+        Some((_, 0)) => anyhow::bail!("nothing to erase"),
+        Some((addr, len)) => {
+            let end = range_end(addr, len as usize)?;
+            println!("Erasing 0x{:08x}..=0x{:08x}", addr, end);
+            serial.send_command_with_progress(
+                commands::FlashErase { start: addr, end },
+                &mut progress,
+            )?;
+        }
+    }
+    println!("\rErase done in {:.1}s", start.elapsed().as_secs_f32());
+    Ok(())
+}
 
-    // Clock PLL set. clk_set
-    let _ = serial.send_command(commands::ClockSet::default())?;
+/// Dump `addr, len` of flash to `output`, or all of it if `None`
+fn read<T: Transport>(
+    serial: &mut Session<T>,
+    range: Option<(u32, u32)>,
+    output: &Path,
+) -> Result<()> {
+    serial.set_flash_para()?;
+    let (addr, len) = match range {
+        Some(range) => range,
+        None => {
+            let jedec_id = serial.send_command(commands::FlashReadJedecId)?;
+            let size = flash::capacity_from_jedec(&jedec_id)
+                .ok_or_else(|| anyhow::anyhow!("unknown flash size, jedec id {:02x?}", jedec_id))?;
+            (0, size)
+        }
+    };
+    let end = addr
+        .checked_add(len)
+        .ok_or_else(|| anyhow::anyhow!("flash range 0x{:x} + 0x{:x} overflows", addr, len))?;
+    println!("Reading 0x{:08x}..0x{:08x}", addr, end);
+
+    let start = Instant::now();
+    let data = serial.dump_flash(addr, len, &mut |done| {
+        print!("\rRead {}/{} bytes", done, len);
+        let _ = io::stdout().flush();
+    })?;
+    std::fs::write(output, &data)?;
+    println!(
+        "\rRead {} bytes in {:.1}s, sha256 verified => {}",
+        data.len(),
+        start.elapsed().as_secs_f32(),
+        output.display()
+    );
+    Ok(())
+}
+
+/// Convert a vendor flash `.conf` into a `FlashSetPara` flash config blob
+fn flash_conf(conf: &Path, output: &Path) -> Result<()> {
+    let cfg = SpiFlashCfg::from_conf(&std::fs::read_to_string(conf)?)?;
+    std::fs::write(output, cfg.to_bytes())?;
+    match flash::Vendor::from_mid(cfg.mid) {
+        Some(vendor) => println!("{} flash config => {}", vendor, output.display()),
+        None => println!("flash config => {}", output.display()),
+    }
+    Ok(())
+}
+
+fn log<T: Transport>(serial: &mut Session<T>, json: bool) -> Result<()> {
+    let log = BootLog::parse(&serial.send_command(commands::LogRead)?);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&log)?);
+        return Ok(());
+    }
+    print!("{}", log);
+    let failures = log.failures().count();
+    if failures > 0 {
+        println!("{} failure(s) logged", failures);
+    }
+    Ok(())
+}
+
+fn mem<T: Transport>(serial: &mut Session<T>, command: MemCommands) -> Result<()> {
+    match command {
+        MemCommands::Read { addr, count } => {
+            count
+                .checked_sub(1)
+                .and_then(|last| last.checked_mul(4))
+                .and_then(|offset| addr.checked_add(offset))
+                .ok_or_else(|| anyhow::anyhow!("bad word range 0x{:x} x {}", addr, count))?;
+            for i in 0..count {
+                let addr = addr + 4 * i;
+                println!("0x{:08x}: 0x{:08x}", addr, serial.read_reg(addr)?);
+            }
+        }
+        MemCommands::Write { addr, value } => {
+            serial.write_reg(addr, value)?;
+            println!("0x{:08x}: 0x{:08x}", addr, serial.read_reg(addr)?);
+        }
+    }
+    Ok(())
+}
+
+fn flash_protection<T: Transport>(serial: &mut Session<T>, command: FlashCommands) -> Result<()> {
+    let jedec_id = serial.send_command(commands::FlashReadJedecId)?;
+    let capacity = flash::capacity_from_jedec(&jedec_id)
+        .ok_or_else(|| anyhow::anyhow!("unknown flash size, jedec id {:02x?}", jedec_id))?;
+    let mut status = serial.read_status()?;
+    match command {
+        FlashCommands::Status => (),
+        FlashCommands::Protect => status = serial.write_status(status.protected())?,
+        FlashCommands::Unprotect => status = serial.write_status(status.unprotected())?,
+    }
+    println!("status => {}", status);
+    let range = status.protected_range(capacity);
+    if range.is_empty() {
+        println!("flash is not write protected");
+    } else {
+        println!("write protected 0x{:06x}..0x{:06x}", range.start, range.end);
+    }
+    Ok(())
+}
+
+fn flash<T: Transport>(
+    serial: &mut Session<T>,
+    fname: &Path,
+    boot_header: Option<&Path>,
+    flash_conf: Option<&Path>,
+    verify: Verify,
+) -> Result<()> {
+    let mut firmware = std::fs::read(fname)?;
+    if firmware.is_empty() {
+        anyhow::bail!("{} is empty", fname.display());
+    }
+    if firmware.len() % 16 != 0 {
+        firmware.resize(firmware.len() + 16 - firmware.len() % 16, 0);
+    }
+
+    println!("Firmware size: {}", firmware.len());
+
+    // checked before talking to the device
+    let user_header = match boot_header {
+        Some(path) => {
+            let raw = std::fs::read(path)?;
+            let header = FwHeader::parse(&raw)?;
+            header.verify_image(&firmware)?;
+            // erasing the image's first sector must not take the header with it
+            let header_end = range_end(0, raw.len())?;
+            let sector = |addr: u32| addr / flash::FLASH_SECTOR_SIZE;
+            if sector(header.group_image_offset()) <= sector(header_end) {
+                anyhow::bail!(
+                    "image offset 0x{:x} shares an erase sector with the boot header (0..=0x{:x})",
+                    header.group_image_offset(),
+                    header_end
+                );
+            }
+            Some((header, raw))
+        }
+        None => None,
+    };
+    let flash_cfg = match flash_conf {
+        Some(path) => Some(SpiFlashCfg::from_conf(&std::fs::read_to_string(path)?)?),
+        None => None,
+    };
 
     let mac_addr = serial.send_command(commands::EfuseReadMac)?;
     println!("mac_addr => {:02x?}", mac_addr);
@@ -62,57 +493,80 @@ fn main() -> Result<()> {
     let jedec_id = serial.send_command(commands::FlashReadJedecId)?;
     println!("jedec_id => {:02x?}", jedec_id);
 
-    serial.send_command(commands::FlashSetPara::default())?;
+    let chip = match &flash_cfg {
+        Some(cfg) => {
+            if jedec_id.first() != Some(&cfg.mid) {
+                anyhow::bail!(
+                    "flash config is for mid 0x{:02x}, the flash's jedec id is {:02x?}",
+                    cfg.mid,
+                    jedec_id
+                );
+            }
+            serial.send_command(commands::FlashSetPara::new(cfg))?;
+            None
+        }
+        None => serial.set_flash_para()?,
+    };
+    if let Some(chip) = chip {
+        println!("flash => {}", chip);
+    }
 
-    // flash load
-    // Erase flash from 0x2000 to 0x8d3f
-    serial.send_command(commands::FlashErase {
-        start: 0x0000_2000,
-        end: 0x0000_8d3f,
-    })?;
+    let (header, raw_header) = user_header.unwrap_or_else(|| {
+        // the ROM boots with the header's flash config, not FlashSetPara's,
+        // so only a listed part's or the user's goes there
+        let mut builder = FwHeaderBuilder::new(&firmware);
+        match flash_cfg.or_else(|| chip.map(|chip| chip.config())) {
+            Some(cfg) => builder = builder.flash_cfg(cfg),
+            None => log::warn!(
+                "no checked config for flash {:02x?}, the boot header keeps the template's, \
+                 pass --flash-conf to use the vendor's",
+                jedec_id
+            ),
+        }
+        let header = builder.build();
+        (header, header.to_bytes().to_vec())
+    });
+    println!(
+        "boot header ok, image at 0x{:x}",
+        header.group_image_offset()
+    );
+    // flash load, as (address, data) regions
+    let regions = [(0, raw_header), (header.group_image_offset(), firmware)];
+    let ends = regions
+        .iter()
+        .map(|(addr, data)| range_end(*addr, data.len()))
+        .collect::<Result<Vec<_>>>()?;
 
+    check_unprotected(serial)?;
 
-    let mut start_addr = 0x2000;
-    for chunk in firmware.chunks(2*1024) {
-        let len = chunk.len();
-        let end_addr = start_addr + len as u32 - 1;
-        println!("flash write {:04x}..{:04x}", start_addr, end_addr);
-        serial.send_command(commands::FlashWrite {
-            start_addr,
-            data: chunk.to_vec(),
+    for ((addr, data), &end) in regions.iter().zip(&ends) {
+        println!("flash erase {:04x}..{:04x}", addr, end);
+        serial.send_command(commands::FlashErase { start: *addr, end })?;
+
+        serial.write_flash(*addr, data, &mut |sent, total| {
+            print!("\rflash write {}/{} bytes", sent, total);
+            let _ = io::stdout().flush();
         })?;
-        start_addr += len as u32;
+        println!("\nFlash done {}", data.len());
     }
-    println!("Flash done {}", firmware.len());
 
     // write check
     serial.send_command(commands::FlashWriteCheck)?;
-    serial.send_command(commands::FlashXipReadStart)?;
-    let sha_address = serial.send_command(commands::FlashXipReadSha {
-        start_addr: 0x0000_2000,
-        len: 0x0000_6d40,
-    })?;
-    println!("=> {:02x?}", sha_address);
-
-    serial.send_command(commands::FlashXipReadFinish)?;
+    let mode = match verify {
+        Verify::Xip => Some(ShaMode::Xip),
+        Verify::Flash => Some(ShaMode::Flash),
+        Verify::None => None,
+    };
+    if let Some(mode) = mode {
+        for ((addr, data), end) in regions.iter().zip(&ends) {
+            serial.verify_flash(*addr, data, mode)?;
+            println!("verify {:04x}..={:04x} ok", addr, end);
+        }
+    }
 
     println!("Hello, world!");
-/*
-    let ret = serial.send_command(commands::FlashRead {
-        start_addr: 0x0000_0000,
-        len: 0x0000_1000,
-    })?;
-*/
-
-    // let ret = serial.send_command(commands::LogRead)?;
-    // println!("log read => {:02x?}", ret);
 
     serial.send_command(commands::Reset)?;
-    // bootinfo.bin
-    // chips/bl616/img_create_mcu/bootinfo.bin
-
-    // img.bin
-    // chips/bl616/img_create_mcu/img.bin
 
     Ok(())
 }
@@ -0,0 +1,960 @@
+//! Connected, chip-identified ISP session.
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serialport::SerialPort;
+use sha2::{Digest, Sha256};
+
+use crate::commands::{self, BootInfo, Command};
+use crate::error::{Error, ErrorCode, Result};
+use crate::flash::{
+    flash_config, xz_compress, FlashChip, StatusRegister, Vendor, COMPRESS_THRESHOLD,
+    FLASH_READ_CHUNK, FLASH_WRITE_CHUNK, READ_ATTEMPTS, READ_STATUS_REG_1, READ_STATUS_REG_2,
+    WRITE_STATUS_REG,
+};
+use crate::image::{RamImage, SEGMENT_CHUNK};
+use crate::transport::{SerialControl, Transport};
+
+pub const DEFAULT_BAUD_RATE: u32 = 115200;
+
+/// Standard UART speeds tried on fallback, fastest first
+pub const BAUD_RATES: [u32; 5] = [3_000_000, 2_000_000, 1_000_000, 500_000, 230_400];
+
+#[derive(Debug, Clone)]
+pub struct SyncOptions {
+    /// Initial UART baud rate, also decides the sync length
+    pub baud_rate: u32,
+    /// Extra sync attempts after the first one
+    pub retries: usize,
+    /// How long to wait for the `OK` of each sync attempt
+    pub timeout: Duration,
+}
+
+impl Default for SyncOptions {
+    fn default() -> Self {
+        Self {
+            baud_rate: DEFAULT_BAUD_RATE,
+            retries: 3,
+            timeout: Duration::from_millis(500),
+        }
+    }
+}
+
+/// Number of 0x55 bytes that fill 6ms of line time
+pub fn sync_len(baud_rate: u32) -> usize {
+    (0.006 * baud_rate as f64 / 10.0) as usize
+}
+
+/// Run the UART sync, returns once the device answered `OK`
+pub fn sync<T: Transport>(transport: &mut T, options: &SyncOptions) -> Result<()> {
+    let sync_bytes = vec![0x55_u8; sync_len(options.baud_rate)];
+    transport.set_timeout(options.timeout)?;
+
+    for attempt in 0..=options.retries {
+        transport.write_bytes(&sync_bytes)?;
+        // input is not flushed, skip whatever garbage precedes the OK
+        let deadline = Instant::now() + options.timeout;
+        let mut last = [0u8; 2];
+        while Instant::now() < deadline {
+            match transport.read_bytes(1) {
+                Ok(b) => {
+                    last = [last[1], b[0]];
+                    if &last == b"OK" {
+                        return Ok(());
+                    }
+                }
+                Err(e) => {
+                    log::debug!("sync attempt {}: {}", attempt, e);
+                    break;
+                }
+            }
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+    Err(Error::Sync(options.retries + 1))
+}
+
+fn check_aligned(addr: u32) -> Result<()> {
+    if !addr.is_multiple_of(4) {
+        return Err(Error::Custom(format!(
+            "register address 0x{:08x} is not word aligned",
+            addr
+        )));
+    }
+    Ok(())
+}
+
+/// Whether `data` hashes to the `actual` SHA-256 reported for `addr`
+fn check_sha256(addr: u32, data: &[u8], actual: &[u8; 32]) -> Result<()> {
+    let expected = Sha256::digest(data);
+    if expected[..] != actual[..] {
+        return Err(Error::Verify {
+            addr,
+            len: data.len() as u32,
+            expected: hex::encode(expected),
+            actual: hex::encode(actual),
+        });
+    }
+    Ok(())
+}
+
+/// How the device computes the SHA-256 of flash contents
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShaMode {
+    /// `FlashReadSha`, reads through the SPI flash controller
+    Flash,
+    /// `FlashXipReadSha`, reads through the XIP cache
+    Xip,
+}
+
+/// Program the session is talking to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    BootRom,
+    /// RAM flash loader started by [`Session::start_flash_loader`]
+    FlashLoader,
+}
+
+pub struct Session<T> {
+    transport: T,
+    options: SyncOptions,
+    stage: Stage,
+    baud_rate: u32,
+    boot_info: BootInfo,
+    chip_id: String,
+    /// Whether `FlashDecompressWrite` works, `None` until tried
+    decompress_write: Option<bool>,
+    /// Flash JEDEC id, `None` until read
+    jedec_id: Option<Vec<u8>>,
+}
+
+impl Session<Box<dyn SerialPort>> {
+    /// Open a serial port and connect with default sync options
+    pub fn open(path: &str, baud_rate: u32) -> Result<Self> {
+        Self::open_with(
+            path,
+            &SyncOptions {
+                baud_rate,
+                ..Default::default()
+            },
+        )
+    }
+
+    pub fn open_with(path: &str, options: &SyncOptions) -> Result<Self> {
+        let mut port = serialport::new(path, options.baud_rate)
+            .timeout(options.timeout)
+            .open()?;
+        port.clear_input()?;
+        Self::connect(port, options)
+    }
+}
+
+impl<T: Transport> Session<T> {
+    /// Sync over an already opened transport, then identify the chip
+    pub fn connect(mut transport: T, options: &SyncOptions) -> Result<Self> {
+        sync(&mut transport, options)?;
+
+        let boot_info = transport.send_command(commands::GetBootInfo)?;
+        log::debug!("boot info => {:?}", boot_info);
+        let chip_id = transport.send_command(commands::GetChipId)?;
+        log::debug!("chip id => {:?}", chip_id);
+
+        Ok(Self {
+            transport,
+            options: options.clone(),
+            stage: Stage::BootRom,
+            baud_rate: options.baud_rate,
+            boot_info,
+            chip_id,
+            decompress_write: None,
+            jedec_id: None,
+        })
+    }
+
+    /// Current UART baud rate of both host and device
+    pub fn baud_rate(&self) -> u32 {
+        self.baud_rate
+    }
+
+    pub fn boot_info(&self) -> &BootInfo {
+        &self.boot_info
+    }
+
+    /// Chip id string reported by GetChipId, trailing NULs stripped
+    pub fn chip_id(&self) -> &str {
+        self.chip_id.trim_end_matches('\0')
+    }
+
+    pub fn stage(&self) -> Stage {
+        self.stage
+    }
+
+    /// Send `cmd`, error codes of the flash loader become [`Error::FlashLoader`]
+    pub fn send_command<C: Command>(&mut self, cmd: C) -> Result<C::Response> {
+        self.send_command_with_progress(cmd, &mut |_| {})
+    }
+
+    /// Send a long running `cmd`, `progress` gets the time elapsed on each `PD` ack
+    pub fn send_command_with_progress<C: Command>(
+        &mut self,
+        cmd: C,
+        progress: &mut dyn FnMut(Duration),
+    ) -> Result<C::Response> {
+        let ret = self.transport.send_command_with_progress(cmd, progress);
+        match (self.stage, ret) {
+            (Stage::FlashLoader, Err(Error::Code(code))) => Err(Error::FlashLoader(code)),
+            (_, ret) => ret,
+        }
+    }
+
+    /// Read a 32-bit register (or any aligned word of memory)
+    pub fn read_reg(&mut self, addr: u32) -> Result<u32> {
+        check_aligned(addr)?;
+        let raw = self.send_command(commands::MemRead {
+            start_addr: addr,
+            len: 4,
+        })?;
+        let raw: [u8; 4] = raw[..].try_into().map_err(|_| {
+            Error::Custom(format!("MemRead returned {} bytes, expected 4", raw.len()))
+        })?;
+        Ok(u32::from_le_bytes(raw))
+    }
+
+    /// Write a 32-bit register (or any aligned word of memory)
+    pub fn write_reg(&mut self, addr: u32, value: u32) -> Result<()> {
+        check_aligned(addr)?;
+        self.send_command(commands::MemWrite {
+            start_addr: addr,
+            data: value.to_le_bytes().to_vec(),
+        })
+    }
+
+    /// Read-modify-write the bits of `mask` to `value`
+    pub fn modify_reg(&mut self, addr: u32, mask: u32, value: u32) -> Result<u32> {
+        let old = self.read_reg(addr)?;
+        let new = (old & !mask) | (value & mask);
+        self.write_reg(addr, new)?;
+        Ok(new)
+    }
+
+    /// Identify the flash by its JEDEC id and send its `FlashSetPara`.
+    ///
+    /// Parts not in [`FLASH_CHIPS`](crate::flash::FLASH_CHIPS) are warned
+    /// about and get the generic config of their vendor, or the eflash loader
+    /// default.
+    pub fn set_flash_para(&mut self) -> Result<Option<&'static FlashChip>> {
+        let jedec_id = self.send_command(commands::FlashReadJedecId)?;
+        self.jedec_id = Some(jedec_id.clone());
+        let chip = FlashChip::lookup(&jedec_id);
+        let vendor = jedec_id.first().copied().and_then(Vendor::from_mid);
+        match (chip, vendor) {
+            (Some(chip), _) => log::info!("flash {}", chip),
+            (None, Some(vendor)) => log::warn!(
+                "{} flash, jedec id {:02x?}, has no checked config, using a generic one",
+                vendor,
+                jedec_id
+            ),
+            (None, None) => log::warn!(
+                "unknown flash, jedec id {:02x?}, using a generic config",
+                jedec_id
+            ),
+        }
+        self.send_command(commands::FlashSetPara::new(&flash_config(&jedec_id)))?;
+        Ok(chip)
+    }
+
+    /// Whether the flash's status registers can be read and written as a
+    /// [`StatusRegister`]
+    pub fn has_known_status_layout(&mut self) -> Result<bool> {
+        Ok(StatusRegister::layout_known(self.flash_jedec_id()?))
+    }
+
+    fn flash_jedec_id(&mut self) -> Result<&[u8]> {
+        if self.jedec_id.is_none() {
+            self.jedec_id = Some(self.send_command(commands::FlashReadJedecId)?);
+        }
+        Ok(self.jedec_id.as_deref().unwrap_or_default())
+    }
+
+    fn check_status_layout(&mut self) -> Result<()> {
+        if self.has_known_status_layout()? {
+            return Ok(());
+        }
+        Err(Error::Custom(format!(
+            "status register layout of flash {:02x?} is unknown",
+            self.flash_jedec_id()?
+        )))
+    }
+
+    /// Read flash status registers SR1 and SR2, refused for flash with
+    /// another layout
+    pub fn read_status(&mut self) -> Result<StatusRegister> {
+        self.check_status_layout()?;
+        let mut sr = [0u8; 2];
+        for (byte, cmd) in sr.iter_mut().zip([READ_STATUS_REG_1, READ_STATUS_REG_2]) {
+            let raw = self.send_command(commands::FlashReadStatusReg { cmd, len: 1 })?;
+            *byte = *raw
+                .first()
+                .ok_or_else(|| Error::Custom("FlashReadStatusReg returned no data".to_string()))?;
+        }
+        Ok(StatusRegister::from_bytes(sr[0], sr[1]))
+    }
+
+    /// Write SR1 and SR2 and wait for the flash to finish
+    pub fn write_status(&mut self, sr: StatusRegister) -> Result<StatusRegister> {
+        self.check_status_layout()?;
+        self.send_command(commands::FlashWriteStatusReg {
+            cmd: WRITE_STATUS_REG,
+            len: 2,
+            data: sr.0 as u32,
+        })?;
+        // tW is 15ms max on most parts
+        let deadline = Instant::now() + Duration::from_millis(100);
+        loop {
+            let now = self.read_status()?;
+            if !now.is_busy() {
+                return Ok(now);
+            }
+            if Instant::now() > deadline {
+                return Err(Error::Timeout);
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    /// Erase the whole flash, returns the time it took
+    pub fn erase_chip(&mut self, progress: &mut dyn FnMut(Duration)) -> Result<Duration> {
+        let start = Instant::now();
+        self.send_command_with_progress(commands::FlashChipErase, progress)?;
+        Ok(start.elapsed())
+    }
+
+    /// Program erased flash at `addr` with `data`.
+    ///
+    /// Larger images go xz compressed when the device supports
+    /// `FlashDecompressWrite`, else plain `FlashWrite` chunks. `progress` gets
+    /// the bytes sent so far and the total, compressed if so.
+    pub fn write_flash(
+        &mut self,
+        addr: u32,
+        data: &[u8],
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<()> {
+        if data.len() >= COMPRESS_THRESHOLD && self.decompress_write != Some(false) {
+            let compressed = xz_compress(data)?;
+            if compressed.len() < data.len() {
+                log::info!("compressed {} bytes to {}", data.len(), compressed.len());
+                match self.write_compressed(addr, &compressed, progress) {
+                    // rejected right away, nothing written yet
+                    Err(e)
+                        if e.code() == Some(ErrorCode::CmdId)
+                            && self.decompress_write.is_none() =>
+                    {
+                        log::info!("FlashDecompressWrite not supported, writing uncompressed");
+                        self.decompress_write = Some(false);
+                    }
+                    ret => return ret,
+                }
+            }
+        }
+
+        let mut start_addr = addr;
+        for chunk in data.chunks(FLASH_WRITE_CHUNK) {
+            self.send_command(commands::FlashWrite {
+                start_addr,
+                data: chunk.to_vec(),
+            })?;
+            start_addr += chunk.len() as u32;
+            progress((start_addr - addr) as usize, data.len());
+        }
+        Ok(())
+    }
+
+    fn write_compressed(
+        &mut self,
+        addr: u32,
+        compressed: &[u8],
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<()> {
+        let mut sent = 0;
+        for chunk in compressed.chunks(FLASH_WRITE_CHUNK) {
+            self.send_command(commands::FlashDecompressWrite {
+                start_addr: addr + sent as u32,
+                data: chunk.to_vec(),
+            })?;
+            self.decompress_write = Some(true);
+            sent += chunk.len();
+            progress(sent, compressed.len());
+        }
+        Ok(())
+    }
+
+    /// Read `len` bytes of flash at `addr`, in chunks retried on transient
+    /// errors. `progress` gets the number of bytes read so far.
+    pub fn read_flash(
+        &mut self,
+        addr: u32,
+        len: u32,
+        progress: &mut dyn FnMut(u32),
+    ) -> Result<Vec<u8>> {
+        let end = addr.checked_add(len).ok_or_else(|| {
+            Error::Custom(format!("flash range 0x{:x} + 0x{:x} overflows", addr, len))
+        })?;
+        let mut data = Vec::with_capacity(len as usize);
+        let mut start_addr = addr;
+        while start_addr < end {
+            let len = (end - start_addr).min(FLASH_READ_CHUNK);
+            let mut attempt = 1;
+            let chunk = loop {
+                let ret = self
+                    .send_command(commands::FlashRead { start_addr, len })
+                    .and_then(|chunk| {
+                        if chunk.len() == len as usize {
+                            Ok(chunk)
+                        } else {
+                            Err(Error::Custom(format!(
+                                "FlashRead returned {} bytes, expected {}",
+                                chunk.len(),
+                                len
+                            )))
+                        }
+                    });
+                match ret {
+                    Ok(chunk) => break chunk,
+                    Err(e) if attempt < READ_ATTEMPTS => {
+                        log::warn!("read @0x{:08x} failed: {}, retrying", start_addr, e);
+                        attempt += 1;
+                        self.discard_input();
+                    }
+                    Err(e) => return Err(e),
+                }
+            };
+            data.extend_from_slice(&chunk);
+            start_addr += len;
+            progress(start_addr - addr);
+        }
+        Ok(data)
+    }
+
+    /// SHA-256 of a flash range computed on device.
+    ///
+    /// Uses `FlashReadSha`, falling back to reading through XIP for ROMs
+    /// that don't know it.
+    pub fn flash_sha256(&mut self, addr: u32, len: u32) -> Result<[u8; 32]> {
+        match self.flash_sha256_with(addr, len, ShaMode::Flash) {
+            Err(e) if e.code() == Some(ErrorCode::CmdId) => {
+                log::debug!("FlashReadSha not supported, using XIP");
+                self.flash_sha256_with(addr, len, ShaMode::Xip)
+            }
+            ret => ret,
+        }
+    }
+
+    /// SHA-256 of a flash range computed on device with the given command
+    pub fn flash_sha256_with(&mut self, addr: u32, len: u32, mode: ShaMode) -> Result<[u8; 32]> {
+        let ret = match mode {
+            ShaMode::Flash => self.send_command(commands::FlashReadSha {
+                start_addr: addr,
+                len,
+            })?,
+            ShaMode::Xip => {
+                self.send_command(commands::FlashXipReadStart)?;
+                let ret = self.send_command(commands::FlashXipReadSha {
+                    start_addr: addr,
+                    len,
+                });
+                self.send_command(commands::FlashXipReadFinish)?;
+                ret?
+            }
+        };
+        ret[..]
+            .try_into()
+            .map_err(|_| Error::Custom(format!("flash sha256 returned {} bytes", ret.len())))
+    }
+
+    /// Check flash at `addr` holds `data`, by SHA-256 on both sides
+    pub fn verify_flash(&mut self, addr: u32, data: &[u8], mode: ShaMode) -> Result<()> {
+        let len = data.len() as u32;
+        let actual = self.flash_sha256_with(addr, len, mode)?;
+        check_sha256(addr, data, &actual)
+    }
+
+    /// [`Session::read_flash`] checked against [`Session::flash_sha256`]
+    pub fn dump_flash(
+        &mut self,
+        addr: u32,
+        len: u32,
+        progress: &mut dyn FnMut(u32),
+    ) -> Result<Vec<u8>> {
+        let data = self.read_flash(addr, len, progress)?;
+        let actual = self.flash_sha256(addr, len)?;
+        check_sha256(addr, &data, &actual)?;
+        Ok(data)
+    }
+
+    /// Drop whatever is left of a failed response
+    fn discard_input(&mut self) {
+        let _ = self.transport.set_timeout(Duration::from_millis(20));
+        while self.transport.read_bytes(1).is_ok() {}
+    }
+
+    /// Set or clear the quad-enable bit, keeping the other bits
+    pub fn set_quad_enable(&mut self, enable: bool) -> Result<StatusRegister> {
+        let sr = self.read_status()?;
+        if sr.quad_enable() == enable {
+            return Ok(sr);
+        }
+        self.write_status(sr.with_quad_enable(enable))
+    }
+
+    /// Download `image` through the BootROM image loader and have it checked
+    pub fn load_image(&mut self, image: &RamImage) -> Result<()> {
+        self.transport.send_command(commands::LoadBootHeader {
+            header: image.boot_header.clone(),
+        })?;
+        for segment in &image.segments {
+            let header = segment.header();
+            let echo = self.transport.send_command(commands::LoadSegHeader {
+                header: header.to_vec(),
+            })?;
+            if !echo.is_empty() && echo != header {
+                return Err(Error::Custom(format!(
+                    "segment header echo mismatch: sent {}, got {}",
+                    hex::encode(header),
+                    hex::encode(&echo)
+                )));
+            }
+            for chunk in segment.data.chunks(SEGMENT_CHUNK) {
+                self.transport.send_command(commands::LoadSegData {
+                    data: chunk.to_vec(),
+                })?;
+            }
+        }
+        self.transport.send_command(commands::CheckImage)
+    }
+
+    /// Load `image` into RAM and jump to it, the session is gone afterwards
+    pub fn run_image(&mut self, image: &RamImage) -> Result<()> {
+        self.load_image(image)?;
+        log::info!("running image at 0x{:08x}", image.entry());
+        self.transport.send_command(commands::RunImage)
+    }
+
+    /// Run the eflash loader `image` from RAM and sync with it.
+    ///
+    /// Commands go to the loader afterwards, it runs at the current baud rate.
+    pub fn start_flash_loader(&mut self, image: &RamImage) -> Result<()> {
+        if self.stage == Stage::FlashLoader {
+            return Err(Error::Custom("flash loader already running".to_string()));
+        }
+        self.run_image(image)?;
+        // give it time to set up clocks and the UART
+        thread::sleep(Duration::from_millis(100));
+        let options = SyncOptions {
+            baud_rate: self.baud_rate,
+            ..self.options.clone()
+        };
+        sync(&mut self.transport, &options)?;
+        self.stage = Stage::FlashLoader;
+        self.decompress_write = None;
+        log::info!("flash loader running");
+        Ok(())
+    }
+
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    pub fn transport_mut(&mut self) -> &mut T {
+        &mut self.transport
+    }
+
+    pub fn into_inner(self) -> T {
+        self.transport
+    }
+}
+
+impl<T: Transport + SerialControl> Session<T> {
+    /// Send ClockSet asking for `baud_rate`, then reconfigure the host and re-sync.
+    ///
+    /// When the link does not come up, slower rates from [`BAUD_RATES`] are tried
+    /// in turn. Returns the rate in use afterwards, which stays the current one if
+    /// every faster rate failed or the transport can't change its speed.
+    pub fn switch_baud_rate(&mut self, baud_rate: u32) -> Result<u32> {
+        let current = self.baud_rate;
+        if !self.transport.can_set_baud_rate() {
+            if baud_rate > current {
+                log::warn!("transport can't change the baud rate, keeping {}", current);
+            }
+            return self.keep_baud_rate();
+        }
+        let candidates = Some(baud_rate).into_iter().chain(
+            BAUD_RATES
+                .iter()
+                .copied()
+                .filter(|&rate| rate < baud_rate && rate > current),
+        );
+        for rate in candidates {
+            if rate <= current {
+                break;
+            }
+            match self.try_baud_rate(rate) {
+                Ok(()) => {
+                    log::info!("baud rate switched to {}", rate);
+                    return Ok(rate);
+                }
+                Err(e) => log::warn!("baud rate {} failed: {}", rate, e),
+            }
+        }
+        self.keep_baud_rate()
+    }
+
+    /// ClockSet at the current speed, clocks still need to be set up
+    fn keep_baud_rate(&mut self) -> Result<u32> {
+        self.transport
+            .send_command(commands::ClockSet::with_load_speed(self.baud_rate))?;
+        Ok(self.baud_rate)
+    }
+
+    fn try_baud_rate(&mut self, baud_rate: u32) -> Result<()> {
+        let current = self.baud_rate;
+        self.transport
+            .send_command(commands::ClockSet::with_load_speed(baud_rate))?;
+        self.transport.set_baud_rate(baud_rate)?;
+        thread::sleep(Duration::from_millis(10));
+
+        let options = SyncOptions {
+            baud_rate,
+            ..self.options.clone()
+        };
+        if let Err(e) = sync(&mut self.transport, &options) {
+            // back to the old speed, the ROM autobauds on the sync pattern
+            self.transport.set_baud_rate(current)?;
+            let options = SyncOptions {
+                baud_rate: current,
+                ..self.options.clone()
+            };
+            sync(&mut self.transport, &options)?;
+            return Err(e);
+        }
+        self.baud_rate = baud_rate;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image::RamImage;
+    use crate::sim::Simulator;
+
+    #[test]
+    fn connect() {
+        let session = Session::connect(Simulator::new(), &SyncOptions::default()).unwrap();
+        assert_eq!(session.chip_id(), "CHIPWB03A00_BL");
+        assert_eq!(
+            session.boot_info().chip_id,
+            vec![0xfb, 0xaf, 0x35, 0xcf, 0x0e, 0xb4]
+        );
+        assert!(session.transport().is_synced());
+    }
+
+    #[test]
+    fn switch_baud_rate() {
+        let mut session = Session::connect(Simulator::new(), &SyncOptions::default()).unwrap();
+        assert_eq!(session.switch_baud_rate(1_000_000).unwrap(), 1_000_000);
+        assert_eq!(session.transport().load_speed(), 1_000_000);
+        session.send_command(commands::GetChipId).unwrap();
+    }
+
+    #[test]
+    fn switch_baud_rate_fallback() {
+        let mut sim = Simulator::new();
+        sim.max_baud_rate = 1_000_000;
+        let mut session = Session::connect(sim, &SyncOptions::default()).unwrap();
+        assert_eq!(session.switch_baud_rate(3_000_000).unwrap(), 1_000_000);
+        assert_eq!(session.baud_rate(), 1_000_000);
+        session.send_command(commands::GetChipId).unwrap();
+
+        let mut sim = Simulator::new();
+        sim.max_baud_rate = 115200;
+        let mut session = Session::connect(sim, &SyncOptions::default()).unwrap();
+        assert_eq!(session.switch_baud_rate(2_000_000).unwrap(), 115200);
+        session.send_command(commands::GetChipId).unwrap();
+    }
+
+    #[test]
+    fn switch_baud_rate_fixed() {
+        // raw TCP bridge
+        let mut sim = Simulator::new();
+        sim.fixed_baud_rate = true;
+        let mut session = Session::connect(sim, &SyncOptions::default()).unwrap();
+        assert_eq!(session.switch_baud_rate(2_000_000).unwrap(), 115200);
+        assert_eq!(session.transport().load_speed(), 115200);
+        session.send_command(commands::GetChipId).unwrap();
+    }
+
+    #[test]
+    fn registers() {
+        let mut session = Session::connect(Simulator::new(), &SyncOptions::default()).unwrap();
+        assert_eq!(session.read_reg(0x2000_0010).unwrap(), 0);
+        session.write_reg(0x2000_0010, 0x1234_5678).unwrap();
+        assert_eq!(session.read_reg(0x2000_0010).unwrap(), 0x1234_5678);
+        assert_eq!(
+            session
+                .modify_reg(0x2000_0010, 0x0000_ff00, 0xaaaa_aaaa)
+                .unwrap(),
+            0x1234_aa78
+        );
+        assert_eq!(session.transport().registers[&0x2000_0010], 0x1234_aa78);
+
+        assert!(session.read_reg(0x2000_0002).is_err());
+        let err = session.read_reg(0x1000_0000).unwrap_err();
+        assert_eq!(err.code(), Some(ErrorCode::MemReadAddr));
+    }
+
+    #[test]
+    fn memory() {
+        let mut session = Session::connect(Simulator::new(), &SyncOptions::default()).unwrap();
+        session
+            .send_command(commands::MemWrite {
+                start_addr: 0x62fc_0001,
+                data: vec![1, 2, 3],
+            })
+            .unwrap();
+        let data = session
+            .send_command(commands::MemRead {
+                start_addr: 0x62fc_0000,
+                len: 5,
+            })
+            .unwrap();
+        assert_eq!(data, [0, 1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn set_flash_para() {
+        let mut sim = Simulator::new();
+        sim.jedec_id = [0xc8, 0x40, 0x17, 0x00];
+        let mut session = Session::connect(sim, &SyncOptions::default()).unwrap();
+        let chip = session.set_flash_para().unwrap().unwrap();
+        assert_eq!(chip.name, "GD25Q64E");
+        let para = session.transport().flash_para.clone().unwrap();
+        assert_eq!(para, chip.config().to_bytes());
+
+        // W25Q64JV, vendor config
+        let mut sim = Simulator::new();
+        sim.jedec_id = [0xef, 0x40, 0x17, 0x00];
+        let mut session = Session::connect(sim, &SyncOptions::default()).unwrap();
+        assert_eq!(session.set_flash_para().unwrap(), None);
+        let para = session.transport().flash_para.clone().unwrap();
+        // mid
+        assert_eq!(para[13], 0xef);
+
+        let mut sim = Simulator::new();
+        sim.jedec_id = [0x12, 0x34, 0x16, 0x00];
+        let mut session = Session::connect(sim, &SyncOptions::default()).unwrap();
+        assert_eq!(session.set_flash_para().unwrap(), None);
+        assert_eq!(
+            session.transport().flash_para.as_deref(),
+            Some(&include_bytes!("../chips/bl616/flash_para.bin")[..])
+        );
+    }
+
+    #[test]
+    fn status_register() {
+        let mut sim = Simulator::new();
+        // factory locked, all of it
+        sim.status_reg = 0x1c;
+        let mut session = Session::connect(sim, &SyncOptions::default()).unwrap();
+        let status = session.read_status().unwrap();
+        assert!(status.is_protected());
+        let write = commands::FlashWrite {
+            start_addr: 0x2000,
+            data: vec![0; 4],
+        };
+        let err = session.send_command(write).unwrap_err();
+        assert_eq!(err.code(), Some(ErrorCode::FlashWrite));
+
+        let status = session.write_status(status.unprotected()).unwrap();
+        assert_eq!(status, StatusRegister(0));
+        session
+            .send_command(commands::FlashWrite {
+                start_addr: 0x2000,
+                data: vec![0; 4],
+            })
+            .unwrap();
+
+        let status = session.set_quad_enable(true).unwrap();
+        assert!(status.quad_enable());
+        assert_eq!(session.transport().status_reg, StatusRegister::QE);
+        let status = session.write_status(status.protected()).unwrap();
+        assert_eq!(status.block_protect(), 7);
+        assert!(status.quad_enable());
+    }
+
+    #[test]
+    fn status_register_unknown_layout() {
+        // MX25L3233F, bit 6 is QE
+        let mut sim = Simulator::new();
+        sim.jedec_id = [0xc2, 0x20, 0x16, 0x00];
+        sim.status_reg = 0x40;
+        let mut session = Session::connect(sim, &SyncOptions::default()).unwrap();
+        assert!(!session.has_known_status_layout().unwrap());
+        assert!(session.read_status().is_err());
+        assert!(session.write_status(StatusRegister(0)).is_err());
+        assert_eq!(session.transport().status_reg, 0x40);
+    }
+
+    #[test]
+    fn erase_chip() {
+        let mut sim = Simulator::new();
+        sim.flash[0x1000..0x2000].fill(0);
+        sim.status_reg = 0x1c;
+        let mut session = Session::connect(sim, &SyncOptions::default()).unwrap();
+        let err = session.erase_chip(&mut |_| {}).unwrap_err();
+        assert_eq!(err.code(), Some(ErrorCode::FlashErase));
+
+        session.write_status(StatusRegister(0)).unwrap();
+        let mut pending = 0;
+        session.erase_chip(&mut |_| pending += 1).unwrap();
+        // one PD per 64 sectors of 4MiB
+        assert_eq!(pending, 16);
+        assert!(session.transport().flash.iter().all(|&b| b == 0xff));
+    }
+
+    #[test]
+    fn dump_flash() {
+        let mut sim = Simulator::new().with_flash_size(64 * 1024);
+        for (i, b) in sim.flash.iter_mut().enumerate() {
+            *b = (i * 7) as u8;
+        }
+        let expected = sim.flash[0x100..0x100 + 10_000].to_vec();
+        let mut session = Session::connect(sim, &SyncOptions::default()).unwrap();
+
+        // 3 chunks, the first one needs a retry
+        session.transport_mut().crc_errors = 1;
+        let mut done = vec![];
+        let data = session
+            .dump_flash(0x100, 10_000, &mut |n| done.push(n))
+            .unwrap();
+        assert_eq!(data, expected);
+        assert_eq!(done, [4096, 8192, 10_000]);
+
+        session.transport_mut().crc_errors = READ_ATTEMPTS;
+        let err = session.read_flash(0, 16, &mut |_| {}).unwrap_err();
+        assert_eq!(err.code(), Some(ErrorCode::CmdCrc));
+
+        let sha = session.flash_sha256(0x100, 10_000).unwrap();
+        assert_eq!(sha[..], Sha256::digest(&expected)[..]);
+
+        assert!(session
+            .dump_flash(0xffff_f000, 0x2000, &mut |_| {})
+            .is_err());
+    }
+
+    #[test]
+    fn write_flash() {
+        let mut data = vec![0u8; 100 * 1024];
+        for (i, b) in data[..3000].iter_mut().enumerate() {
+            *b = (i * 13) as u8;
+        }
+
+        let mut session = Session::connect(Simulator::new(), &SyncOptions::default()).unwrap();
+        let mut sent = (0, 0);
+        session
+            .write_flash(0x2000, &data, &mut |n, total| sent = (n, total))
+            .unwrap();
+        // went compressed
+        assert!(sent.1 < 4096);
+        assert_eq!(sent.0, sent.1);
+        assert_eq!(session.decompress_write, Some(true));
+        session.verify_flash(0x2000, &data, ShaMode::Flash).unwrap();
+
+        // back to back streams
+        session.write_flash(0x40000, &data, &mut |_, _| {}).unwrap();
+        session
+            .verify_flash(0x40000, &data, ShaMode::Flash)
+            .unwrap();
+
+        let mut sim = Simulator::new();
+        sim.decompress_write = false;
+        let mut session = Session::connect(sim, &SyncOptions::default()).unwrap();
+        session
+            .write_flash(0x2000, &data, &mut |n, total| sent = (n, total))
+            .unwrap();
+        assert_eq!(sent, (data.len(), data.len()));
+        assert_eq!(session.decompress_write, Some(false));
+        session.verify_flash(0x2000, &data, ShaMode::Flash).unwrap();
+    }
+
+    #[test]
+    fn verify_flash() {
+        let mut session = Session::connect(Simulator::new(), &SyncOptions::default()).unwrap();
+        let data: Vec<u8> = (0..5000).map(|i| i as u8).collect();
+        for (i, chunk) in data.chunks(2048).enumerate() {
+            session
+                .send_command(commands::FlashWrite {
+                    start_addr: 0x2000 + 2048 * i as u32,
+                    data: chunk.to_vec(),
+                })
+                .unwrap();
+        }
+        session.verify_flash(0x2000, &data, ShaMode::Xip).unwrap();
+        session.verify_flash(0x2000, &data, ShaMode::Flash).unwrap();
+
+        session.transport_mut().flash[0x2000 + 4000] ^= 0x10;
+        let err = session
+            .verify_flash(0x2000, &data, ShaMode::Flash)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Verify {
+                addr: 0x2000,
+                len: 5000,
+                ..
+            }
+        ));
+        assert!(err
+            .to_string()
+            .starts_with("Verify failed at region 0x00002000..0x00003388"));
+    }
+
+    #[test]
+    fn flash_loader() {
+        let mut session = Session::connect(Simulator::new(), &SyncOptions::default()).unwrap();
+        let err = session
+            .send_command(commands::FlashWrite {
+                start_addr: 0,
+                data: vec![0; 4],
+            })
+            .and_then(|_| {
+                session.send_command(commands::FlashWrite {
+                    start_addr: 0,
+                    data: vec![0xff; 4],
+                })
+            })
+            .unwrap_err();
+        assert!(matches!(err, Error::Code(ErrorCode::FlashWrite)));
+
+        let loader = RamImage::from_binary(0x62fc_0000, vec![0x13; 1024]).unwrap();
+        session.start_flash_loader(&loader).unwrap();
+        assert_eq!(session.stage(), Stage::FlashLoader);
+        assert!(session.transport().is_flash_loader());
+
+        let err = session
+            .send_command(commands::FlashWrite {
+                start_addr: 0,
+                data: vec![0xff; 4],
+            })
+            .unwrap_err();
+        assert!(matches!(err, Error::FlashLoader(ErrorCode::FlashWrite)));
+        assert_eq!(err.code(), Some(ErrorCode::FlashWrite));
+        assert!(session.start_flash_loader(&loader).is_err());
+    }
+
+    #[test]
+    fn sync_len_115200() {
+        assert_eq!(sync_len(115200), 69);
+    }
+}
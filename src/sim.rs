@@ -0,0 +1,839 @@
+//! In-process BootROM simulator.
+//!
+//! [`Simulator`] implements [`Transport`] and answers the UART ISP protocol the
+//! way a BL616 BootROM does, backed by an emulated NOR flash and efuse array.
+//! It allows running the whole `commands::*` set without hardware.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::io::Write;
+
+use sha2::{Digest, Sha256};
+use xz2::write::XzDecoder;
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::flash::{StatusRegister, READ_STATUS_REG_1, READ_STATUS_REG_2, WRITE_STATUS_REG};
+use crate::transport::{SerialControl, Transport};
+use crate::CRC32;
+
+pub const FLASH_SECTOR_SIZE: usize = 4 * 1024;
+
+/// Start of the emulated OCRAM
+pub const RAM_BASE: u32 = 0x62fc_0000;
+const RAM_SIZE: usize = 320 * 1024;
+
+/// Peripheral register space, word access only
+const PERIPH: std::ops::Range<u32> = 0x2000_0000..0x3000_0000;
+
+/// Sectors erased between two `PD` acks
+const SECTORS_PER_PENDING: usize = 64;
+
+/// Progress of a RAM image download
+struct ImageLoad {
+    boot_header: Vec<u8>,
+    /// segments announced so far
+    segments: u32,
+    /// where the next data of the current segment goes
+    dest: u32,
+    /// data of the current segment still expected
+    remaining: usize,
+    hasher: Sha256,
+    checked: bool,
+}
+
+/// Stream of `FlashDecompressWrite` chunks being expanded
+struct XzWrite {
+    /// address field expected in the next chunk
+    next_addr: u32,
+    /// where the next decompressed data goes
+    flash_addr: usize,
+    decoder: XzDecoder<Vec<u8>>,
+}
+
+/// Reply of a handled command
+enum Reply {
+    /// `OK` only
+    Ack,
+    /// `OK` + len + payload
+    Data(Vec<u8>),
+}
+
+pub struct Simulator {
+    /// Emulated NOR flash, erased state is 0xff
+    pub flash: Vec<u8>,
+    /// 256 byte efuse
+    pub efuse: Vec<u8>,
+    /// OCRAM at [`RAM_BASE`]
+    pub ram: Vec<u8>,
+    /// Peripheral registers by address, reading as zero until written
+    pub registers: BTreeMap<u32, u32>,
+    pub jedec_id: [u8; 4],
+    /// Flash config of the last `FlashSetPara`
+    pub flash_para: Option<Vec<u8>>,
+    /// Flash SR1 | SR2 << 8, block protection is enforced on erase and write
+    pub status_reg: u16,
+    pub boot_rom_version: [u8; 4],
+    pub chip_id: String,
+    /// BootROM log returned by LogRead
+    pub log: String,
+    /// Fastest UART speed the emulated link survives
+    pub max_baud_rate: u32,
+    /// Host side speed can't change, as behind a raw TCP bridge
+    pub fixed_baud_rate: bool,
+    /// Entry point of the image started by RunImage
+    pub entry: Option<u32>,
+    /// Number of upcoming commands rejected with `CmdCrc`, as line noise would
+    pub crc_errors: usize,
+    /// Whether `FlashDecompressWrite` is understood
+    pub decompress_write: bool,
+    /// Whether a started image acts as the eflash loader, else the device goes silent
+    pub emulate_flash_loader: bool,
+    flash_loader: bool,
+    image: Option<ImageLoad>,
+    xz: Option<XzWrite>,
+    synced: bool,
+    xip_mode: bool,
+    load_speed: u32,
+    /// Baud rate of the host side, set via `SerialControl::set_baud_rate`
+    host_baud_rate: u32,
+    /// Baud rate the ROM UART currently runs at
+    uart_baud_rate: u32,
+    /// host => device
+    rx: Vec<u8>,
+    /// device => host
+    tx: VecDeque<u8>,
+}
+
+impl Default for Simulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Simulator {
+    /// A BL616 with 4MiB GD25Q32 flash
+    pub fn new() -> Self {
+        let mut efuse = vec![0u8; 256];
+        // wifi mac at 0x14
+        efuse[0x14..0x1a].copy_from_slice(&[0xfb, 0xaf, 0x35, 0xcf, 0x0e, 0xb4]);
+        Self {
+            flash: vec![0xff; 4 * 1024 * 1024],
+            efuse,
+            ram: vec![0; RAM_SIZE],
+            registers: BTreeMap::new(),
+            jedec_id: [0xc8, 0x40, 0x16, 0x00],
+            flash_para: None,
+            // QE set, as shipped on most modules
+            status_reg: StatusRegister::QE,
+            boot_rom_version: [1, 0, 0, 0],
+            chip_id: "CHIPWB03A00_BL\0\0".to_string(),
+            log: String::new(),
+            max_baud_rate: 2_000_000,
+            fixed_baud_rate: false,
+            entry: None,
+            crc_errors: 0,
+            decompress_write: true,
+            emulate_flash_loader: true,
+            flash_loader: false,
+            image: None,
+            xz: None,
+            synced: false,
+            xip_mode: false,
+            load_speed: 115200,
+            host_baud_rate: 115200,
+            uart_baud_rate: 115200,
+            rx: vec![],
+            tx: VecDeque::new(),
+        }
+    }
+
+    pub fn with_flash_size(mut self, size: usize) -> Self {
+        self.flash = vec![0xff; size];
+        self
+    }
+
+    pub fn is_synced(&self) -> bool {
+        self.synced
+    }
+
+    /// Whether the emulated eflash loader answers instead of the BootROM
+    pub fn is_flash_loader(&self) -> bool {
+        self.flash_loader
+    }
+
+    /// Bytes the device has sent but the host not read yet
+    pub fn available(&self) -> usize {
+        self.tx.len()
+    }
+
+    /// UART speed requested by the last ClockSet
+    pub fn load_speed(&self) -> u32 {
+        self.load_speed
+    }
+
+    fn mac(&self) -> [u8; 6] {
+        self.efuse[0x14..0x1a].try_into().unwrap()
+    }
+
+    fn boot_info(&self) -> Vec<u8> {
+        let mut raw = vec![0u8; 24];
+        raw[0..4].copy_from_slice(&self.boot_rom_version);
+        let mut chip_id = self.mac();
+        chip_id.reverse();
+        raw[12..18].copy_from_slice(&chip_id);
+        raw
+    }
+
+    /// Consume bytes written by the host, queue replies for complete frames
+    fn process(&mut self) {
+        if self.entry.is_some() && !self.flash_loader {
+            // user firmware owns the UART now
+            self.rx.clear();
+            return;
+        }
+        loop {
+            // sync bytes at a frame boundary, answered with OK every time
+            let n = self.rx.iter().take_while(|&&b| b == 0x55).count();
+            if n > 0 {
+                self.rx.drain(..n);
+                self.synced = true;
+                self.tx.extend(b"OK");
+                continue;
+            }
+            if !self.synced {
+                // garbage before sync is dropped
+                self.rx.clear();
+                return;
+            }
+            if self.rx.len() < 4 {
+                return;
+            }
+            let len = u16::from_le_bytes([self.rx[2], self.rx[3]]) as usize;
+            if self.rx.len() < 4 + len {
+                return;
+            }
+            let frame: Vec<u8> = self.rx.drain(..4 + len).collect();
+            let reply = self.dispatch(&frame);
+            self.respond(reply);
+        }
+    }
+
+    /// Queue `PD` keep-alives, as sent during long operations
+    fn pending(&mut self, n: usize) {
+        for _ in 0..n {
+            self.tx.extend(b"PD");
+        }
+    }
+
+    fn respond(&mut self, reply: std::result::Result<Reply, ErrorCode>) {
+        match reply {
+            Ok(Reply::Ack) => self.tx.extend(b"OK"),
+            Ok(Reply::Data(payload)) => {
+                self.tx.extend(b"OK");
+                self.tx.extend((payload.len() as u16).to_le_bytes());
+                self.tx.extend(payload);
+            }
+            Err(code) => {
+                self.tx.extend(b"FL");
+                self.tx.extend(code.code().to_le_bytes());
+            }
+        }
+    }
+
+    fn dispatch(&mut self, frame: &[u8]) -> std::result::Result<Reply, ErrorCode> {
+        let cmd = frame[0];
+        let payload = &frame[4..];
+        if !payload.is_empty() {
+            let checksum = frame[2..]
+                .iter()
+                .fold(0_u8, |acc, &c| acc.overflowing_add(c).0);
+            if checksum != frame[1] {
+                return Err(ErrorCode::CmdCrc);
+            }
+        }
+        if self.crc_errors > 0 {
+            self.crc_errors -= 1;
+            return Err(ErrorCode::CmdCrc);
+        }
+
+        if self.flash_loader && (0x11..=0x1a).contains(&cmd) {
+            // image loading is BootROM only
+            return Err(ErrorCode::CmdId);
+        }
+
+        match cmd {
+            // GetChipId
+            0x05 => Ok(Reply::Data(self.chip_id.as_bytes().to_vec())),
+            // GetBootInfo
+            0x10 => Ok(Reply::Data(self.boot_info())),
+            // LoadBootHeader
+            0x11 => {
+                if payload.len() != 256 {
+                    return Err(ErrorCode::ImgBootHeaderLen);
+                }
+                if &payload[..4] != b"BFNP" {
+                    return Err(ErrorCode::ImgBootHeaderMagic);
+                }
+                let crc_ignore = read_u32(payload, 0x78) & (1 << 16) != 0;
+                if !crc_ignore && CRC32.checksum(&payload[..0xfc]) != read_u32(payload, 0xfc) {
+                    return Err(ErrorCode::ImgBootHeaderCrc);
+                }
+                self.image = Some(ImageLoad {
+                    boot_header: payload.to_vec(),
+                    segments: 0,
+                    dest: 0,
+                    remaining: 0,
+                    hasher: Sha256::new(),
+                    checked: false,
+                });
+                Ok(Reply::Ack)
+            }
+            // LoadSegHeader
+            0x17 => {
+                let image = self.image.as_ref().ok_or(ErrorCode::ImgBootHeaderNotLoad)?;
+                if payload.len() != 16 {
+                    return Err(ErrorCode::ImgSectionHeaderLen);
+                }
+                if CRC32.checksum(&payload[..12]) != read_u32(payload, 12) {
+                    return Err(ErrorCode::ImgSectionHeaderCrc);
+                }
+                if image.remaining != 0 {
+                    return Err(ErrorCode::ImgSectionDataTlen);
+                }
+                if image.segments >= read_u32(&image.boot_header, 0x84) {
+                    return Err(ErrorCode::ImgSegmentCnt);
+                }
+                let dest = read_u32(payload, 0);
+                let len = read_u32(payload, 4) as usize;
+                if self.ram_offset(dest, len).is_none() {
+                    return Err(ErrorCode::ImgSectionHeaderDst);
+                }
+                let image = self.image.as_mut().unwrap();
+                image.segments += 1;
+                image.dest = dest;
+                image.remaining = len;
+                image.hasher.update(payload);
+                Ok(Reply::Data(payload.to_vec()))
+            }
+            // LoadSegData
+            0x18 => {
+                let image = self.image.as_ref().ok_or(ErrorCode::ImgBootHeaderNotLoad)?;
+                if payload.len() > image.remaining {
+                    return Err(ErrorCode::ImgSectionDataLen);
+                }
+                let offset = self.ram_offset(image.dest, payload.len()).unwrap();
+                self.ram[offset..offset + payload.len()].copy_from_slice(payload);
+                let image = self.image.as_mut().unwrap();
+                image.dest += payload.len() as u32;
+                image.remaining -= payload.len();
+                image.hasher.update(payload);
+                Ok(Reply::Ack)
+            }
+            // CheckImage
+            0x19 => {
+                let image = self.image.as_mut().ok_or(ErrorCode::ImgBootHeaderNotLoad)?;
+                let header = &image.boot_header;
+                if image.segments != read_u32(header, 0x84) || image.remaining != 0 {
+                    return Err(ErrorCode::ImgHalfBaked);
+                }
+                let hash_ignore = read_u32(header, 0x78) & (1 << 17) != 0;
+                if !hash_ignore && image.hasher.clone().finalize()[..] != header[0x88..0xa8] {
+                    return Err(ErrorCode::ImgHash);
+                }
+                image.checked = true;
+                Ok(Reply::Ack)
+            }
+            // RunImage
+            0x1a => {
+                let image = self.image.take().ok_or(ErrorCode::ImgBootHeaderNotLoad)?;
+                if !image.checked {
+                    return Err(ErrorCode::ImgHalfBaked);
+                }
+                // the ROM is gone once it jumped
+                self.entry = Some(read_u32(&image.boot_header, 0xb0));
+                self.synced = false;
+                self.flash_loader = self.emulate_flash_loader;
+                Ok(Reply::Ack)
+            }
+            // Reset
+            0x21 => {
+                self.entry = None;
+                self.flash_loader = false;
+                self.synced = false;
+                self.xip_mode = false;
+                Ok(Reply::Ack)
+            }
+            // ClockSet
+            0x22 => {
+                if payload.len() < 8 {
+                    return Err(ErrorCode::CmdLen);
+                }
+                self.load_speed = read_u32(payload, 4);
+                self.uart_baud_rate = self.load_speed;
+                Ok(Reply::Ack)
+            }
+            // FlashErase
+            0x30 => {
+                let (start, end) = self.range_param(payload, ErrorCode::FlashEraseParam)?;
+                if start > end {
+                    return Err(ErrorCode::FlashEraseParam);
+                }
+                let start = start / FLASH_SECTOR_SIZE * FLASH_SECTOR_SIZE;
+                let end = ((end / FLASH_SECTOR_SIZE + 1) * FLASH_SECTOR_SIZE).min(self.flash.len());
+                if self.is_write_protected(start, end) {
+                    return Err(ErrorCode::FlashErase);
+                }
+                self.flash[start..end].fill(0xff);
+                self.pending((end - start) / FLASH_SECTOR_SIZE / SECTORS_PER_PENDING);
+                Ok(Reply::Ack)
+            }
+            // FlashWrite
+            0x31 => {
+                if payload.len() < 4 {
+                    return Err(ErrorCode::FlashWriteParam);
+                }
+                let addr = read_u32(payload, 0) as usize;
+                self.program(addr, &payload[4..])?;
+                Ok(Reply::Ack)
+            }
+            // FlashRead
+            0x32 => {
+                if payload.len() != 8 {
+                    return Err(ErrorCode::CmdLen);
+                }
+                let addr = read_u32(payload, 0) as usize;
+                let len = read_u32(payload, 4) as usize;
+                if addr + len > self.flash.len() {
+                    return Err(ErrorCode::FlashBootParam);
+                }
+                Ok(Reply::Data(self.flash[addr..addr + len].to_vec()))
+            }
+            // FlashReadJedecId
+            0x36 => Ok(Reply::Data(self.jedec_id.to_vec())),
+            // FlashReadStatusReg
+            0x37 => {
+                if payload.len() != 8 {
+                    return Err(ErrorCode::CmdLen);
+                }
+                let len = read_u32(payload, 4) as usize;
+                if !(1..=4).contains(&len) {
+                    return Err(ErrorCode::FlashReadStatusReg);
+                }
+                let [sr1, sr2] = self.status_reg.to_le_bytes();
+                let value = match read_u32(payload, 0) {
+                    READ_STATUS_REG_1 => sr1,
+                    READ_STATUS_REG_2 => sr2,
+                    _ => 0,
+                };
+                Ok(Reply::Data(vec![value; len]))
+            }
+            // FlashWriteStatusReg
+            0x38 => {
+                if payload.len() != 12 {
+                    return Err(ErrorCode::CmdLen);
+                }
+                let (sr1, sr2) = (payload[8], payload[9]);
+                match (read_u32(payload, 0), read_u32(payload, 4)) {
+                    (WRITE_STATUS_REG, 1) => {
+                        self.status_reg = self.status_reg & 0xff00 | sr1 as u16
+                    }
+                    (WRITE_STATUS_REG, 2) => self.status_reg = u16::from_le_bytes([sr1, sr2]),
+                    // Write Status Register-2
+                    (0x31, 1) => self.status_reg = self.status_reg & 0x00ff | (sr1 as u16) << 8,
+                    _ => return Err(ErrorCode::FlashWriteStatusReg),
+                }
+                // WIP and WEL are read only
+                self.status_reg &= !(StatusRegister::WIP | StatusRegister::WEL);
+                Ok(Reply::Ack)
+            }
+            // FlashWriteCheck
+            0x3a => Ok(Reply::Ack),
+            // FlashSetPara
+            0x3b => {
+                if payload.len() < 4 {
+                    return Err(ErrorCode::FlashSetParam);
+                }
+                self.flash_para = Some(payload[4..].to_vec());
+                Ok(Reply::Ack)
+            }
+            // FlashChipErase
+            0x3c => {
+                if self.is_write_protected(0, self.flash.len()) {
+                    return Err(ErrorCode::FlashErase);
+                }
+                self.flash.fill(0xff);
+                self.pending(self.flash.len() / FLASH_SECTOR_SIZE / SECTORS_PER_PENDING);
+                Ok(Reply::Ack)
+            }
+            // FlashDecompressWrite
+            0x3f => {
+                if !self.decompress_write {
+                    return Err(ErrorCode::CmdId);
+                }
+                if payload.len() < 4 {
+                    return Err(ErrorCode::FlashWriteParam);
+                }
+                let addr = read_u32(payload, 0);
+                let data = &payload[4..];
+                // a chunk not following the previous one starts a new stream
+                let mut xz = match self.xz.take() {
+                    Some(xz) if xz.next_addr == addr => xz,
+                    _ => XzWrite {
+                        next_addr: addr,
+                        flash_addr: addr as usize,
+                        decoder: XzDecoder::new(Vec::new()),
+                    },
+                };
+                xz.decoder
+                    .write_all(data)
+                    .and_then(|_| xz.decoder.flush())
+                    .map_err(|_| ErrorCode::FlashDecompressWrite)?;
+                let out = std::mem::take(xz.decoder.get_mut());
+                self.program(xz.flash_addr, &out)?;
+                xz.flash_addr += out.len();
+                xz.next_addr += data.len() as u32;
+                self.xz = Some(xz);
+                Ok(Reply::Ack)
+            }
+            // FlashReadSha, FlashXipReadSha
+            0x3d | 0x3e => {
+                if cmd == 0x3e && !self.xip_mode {
+                    return Err(ErrorCode::CmdSeq);
+                }
+                if payload.len() != 8 {
+                    return Err(ErrorCode::CmdLen);
+                }
+                let addr = read_u32(payload, 0) as usize;
+                let len = read_u32(payload, 4) as usize;
+                if addr + len > self.flash.len() {
+                    return Err(ErrorCode::FlashBootParam);
+                }
+                Ok(Reply::Data(
+                    Sha256::digest(&self.flash[addr..addr + len]).to_vec(),
+                ))
+            }
+            // EfuseWrite
+            0x40 => {
+                if payload.len() < 4 {
+                    return Err(ErrorCode::EfuseWriteParam);
+                }
+                let addr = read_u32(payload, 0) as usize;
+                let data = &payload[4..];
+                if !addr.is_multiple_of(4) || addr + data.len() > self.efuse.len() {
+                    return Err(ErrorCode::EfuseWriteAddr);
+                }
+                // fuses can only be blown
+                for (cur, &new) in self.efuse[addr..].iter_mut().zip(data) {
+                    *cur |= new;
+                }
+                Ok(Reply::Ack)
+            }
+            // EfuseRead
+            0x41 => {
+                if payload.len() != 8 {
+                    return Err(ErrorCode::EfuseReadParam);
+                }
+                let addr = read_u32(payload, 0) as usize;
+                let len = read_u32(payload, 4) as usize;
+                if addr + len > self.efuse.len() {
+                    return Err(ErrorCode::EfuseReadAddr);
+                }
+                Ok(Reply::Data(self.efuse[addr..addr + len].to_vec()))
+            }
+            // EfuseReadMac
+            0x42 => {
+                let mut raw = self.mac().to_vec();
+                raw.extend_from_slice(&CRC32.checksum(&raw).to_le_bytes());
+                Ok(Reply::Data(raw))
+            }
+            // MemWrite
+            0x50 => {
+                if payload.len() < 4 {
+                    return Err(ErrorCode::MemWriteParam);
+                }
+                let addr = read_u32(payload, 0);
+                let data = &payload[4..];
+                if let Some(offset) = self.ram_offset(addr, data.len()) {
+                    self.ram[offset..offset + data.len()].copy_from_slice(data);
+                } else if is_register_access(addr, data.len()) {
+                    for (i, word) in data.chunks(4).enumerate() {
+                        self.registers
+                            .insert(addr + 4 * i as u32, read_u32(word, 0));
+                    }
+                } else {
+                    return Err(ErrorCode::MemWriteAddr);
+                }
+                Ok(Reply::Ack)
+            }
+            // MemRead
+            0x51 => {
+                if payload.len() != 8 {
+                    return Err(ErrorCode::MemReadParam);
+                }
+                let addr = read_u32(payload, 0);
+                let len = read_u32(payload, 4) as usize;
+                if let Some(offset) = self.ram_offset(addr, len) {
+                    Ok(Reply::Data(self.ram[offset..offset + len].to_vec()))
+                } else if is_register_access(addr, len) {
+                    let data = (0..len as u32 / 4)
+                        .flat_map(|i| {
+                            let value = self.registers.get(&(addr + 4 * i)).copied();
+                            value.unwrap_or(0).to_le_bytes()
+                        })
+                        .collect();
+                    Ok(Reply::Data(data))
+                } else {
+                    Err(ErrorCode::MemReadAddr)
+                }
+            }
+            // FlashXipReadStart
+            0x60 => {
+                self.xip_mode = true;
+                Ok(Reply::Ack)
+            }
+            // FlashXipReadFinish
+            0x61 => {
+                self.xip_mode = false;
+                Ok(Reply::Ack)
+            }
+            // LogRead
+            0x71 => Ok(Reply::Data(self.log.as_bytes().to_vec())),
+            _ => Err(ErrorCode::CmdId),
+        }
+    }
+
+    /// Offset into `ram` if `addr..addr + len` lies within it
+    fn ram_offset(&self, addr: u32, len: usize) -> Option<usize> {
+        let offset = addr.checked_sub(RAM_BASE)? as usize;
+        (offset + len <= self.ram.len()).then_some(offset)
+    }
+
+    /// NOR program `data` at `addr`, bits can only be cleared
+    fn program(&mut self, addr: usize, data: &[u8]) -> std::result::Result<(), ErrorCode> {
+        if addr + data.len() > self.flash.len() {
+            return Err(ErrorCode::FlashWriteAddr);
+        }
+        if self.is_write_protected(addr, addr + data.len()) {
+            return Err(ErrorCode::FlashWrite);
+        }
+        let target = &mut self.flash[addr..addr + data.len()];
+        if target.iter().zip(data).any(|(&cur, &new)| new & !cur != 0) {
+            return Err(ErrorCode::FlashWrite);
+        }
+        target.copy_from_slice(data);
+        Ok(())
+    }
+
+    /// Whether `start..end` overlaps the block protected area
+    fn is_write_protected(&self, start: usize, end: usize) -> bool {
+        let protected = StatusRegister(self.status_reg).protected_range(self.flash.len() as u32);
+        (start as u32) < protected.end && protected.start < end as u32
+    }
+
+    /// (start, end) parameter pair, checked against flash size
+    fn range_param(
+        &self,
+        payload: &[u8],
+        err: ErrorCode,
+    ) -> std::result::Result<(usize, usize), ErrorCode> {
+        if payload.len() != 8 {
+            return Err(ErrorCode::CmdLen);
+        }
+        let start = read_u32(payload, 0) as usize;
+        let end = read_u32(payload, 4) as usize;
+        if start >= self.flash.len() || end >= self.flash.len() {
+            return Err(err);
+        }
+        Ok((start, end))
+    }
+}
+
+fn is_register_access(addr: u32, len: usize) -> bool {
+    PERIPH.contains(&addr)
+        && addr.is_multiple_of(4)
+        && len.is_multiple_of(4)
+        && addr as usize + len <= PERIPH.end as usize
+}
+
+fn read_u32(raw: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(raw[offset..offset + 4].try_into().unwrap())
+}
+
+impl Transport for Simulator {
+    fn read_bytes(&mut self, n: usize) -> Result<Vec<u8>> {
+        if self.tx.len() < n {
+            return Err(Error::Timeout);
+        }
+        Ok(self.tx.drain(..n).collect())
+    }
+
+    fn write_bytes(&mut self, buf: &[u8]) -> Result<()> {
+        if self.host_baud_rate > self.max_baud_rate {
+            // line noise
+            self.synced = false;
+            return Ok(());
+        }
+        if self.host_baud_rate != self.uart_baud_rate {
+            // the ROM autobauds on the sync pattern, anything else is noise
+            if buf.iter().any(|&b| b != 0x55) {
+                self.synced = false;
+                return Ok(());
+            }
+            self.uart_baud_rate = self.host_baud_rate;
+        }
+        self.rx.extend_from_slice(buf);
+        self.process();
+        Ok(())
+    }
+}
+
+impl SerialControl for Simulator {
+    fn set_baud_rate(&mut self, baud_rate: u32) -> Result<()> {
+        if self.fixed_baud_rate {
+            return Err(Error::Custom("baud rate is fixed".to_string()));
+        }
+        self.host_baud_rate = baud_rate;
+        Ok(())
+    }
+
+    fn can_set_baud_rate(&self) -> bool {
+        !self.fixed_baud_rate
+    }
+
+    fn set_dtr(&mut self, _level: bool) -> Result<()> {
+        Ok(())
+    }
+
+    fn set_rts(&mut self, _level: bool) -> Result<()> {
+        Ok(())
+    }
+
+    fn clear_input(&mut self) -> Result<()> {
+        self.tx.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{self, Command};
+
+    fn synced() -> Simulator {
+        let mut sim = Simulator::new();
+        sim.write_bytes(&[0x55; 69]).unwrap();
+        assert_eq!(sim.read_bytes(2).unwrap(), b"OK");
+        sim
+    }
+
+    #[test]
+    fn info_commands() {
+        let mut sim = synced();
+        let boot_info = sim.send_command(commands::GetBootInfo).unwrap();
+        assert_eq!(boot_info.chip_id, vec![0xfb, 0xaf, 0x35, 0xcf, 0x0e, 0xb4]);
+        let chip_id = sim.send_command(commands::GetChipId).unwrap();
+        assert!(chip_id.starts_with("CHIPWB03A00_BL"));
+        let mac = sim.send_command(commands::EfuseReadMac).unwrap();
+        assert_eq!(*mac, vec![0xfb, 0xaf, 0x35, 0xcf, 0x0e, 0xb4]);
+        let jedec_id = sim.send_command(commands::FlashReadJedecId).unwrap();
+        assert_eq!(jedec_id, vec![0xc8, 0x40, 0x16, 0x00]);
+    }
+
+    #[test]
+    fn bad_checksum() {
+        let mut sim = synced();
+        let mut raw = commands::FlashErase {
+            start: 0,
+            end: 0xfff,
+        }
+        .to_raw();
+        raw[1] ^= 0xff;
+        let err = sim.call_raw_no_resp(&raw).unwrap_err();
+        assert_eq!(err.code(), Some(ErrorCode::CmdCrc));
+    }
+
+    #[test]
+    fn write_requires_erase() {
+        let mut sim = synced();
+        sim.send_command(commands::FlashWrite {
+            start_addr: 0x2000,
+            data: vec![0x00; 16],
+        })
+        .unwrap();
+        let err = sim
+            .send_command(commands::FlashWrite {
+                start_addr: 0x2000,
+                data: vec![0xaa; 16],
+            })
+            .unwrap_err();
+        assert_eq!(err.code(), Some(ErrorCode::FlashWrite));
+
+        sim.send_command(commands::FlashErase {
+            start: 0x2000,
+            end: 0x200f,
+        })
+        .unwrap();
+        sim.send_command(commands::FlashWrite {
+            start_addr: 0x2000,
+            data: vec![0xaa; 16],
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn erase_with_pending() {
+        let mut sim = synced();
+        sim.flash[0x10_0000] = 0;
+        sim.send_command(commands::FlashErase {
+            start: 0,
+            end: 0x20_0000 - 1,
+        })
+        .unwrap();
+        assert_eq!(sim.flash[0x10_0000], 0xff);
+        assert!(sim.read_bytes(1).is_err());
+    }
+
+    #[test]
+    fn flash_and_verify() {
+        let mut sim = synced();
+        let firmware: Vec<u8> = (0..10000).map(|i| (i * 7) as u8).collect();
+
+        sim.send_command(commands::ClockSet::default()).unwrap();
+        sim.send_command(commands::FlashSetPara::default()).unwrap();
+        sim.send_command(commands::FlashErase {
+            start: 0x2000,
+            end: 0x2000 + firmware.len() as u32 - 1,
+        })
+        .unwrap();
+        let mut addr = 0x2000;
+        for chunk in firmware.chunks(2048) {
+            sim.send_command(commands::FlashWrite {
+                start_addr: addr,
+                data: chunk.to_vec(),
+            })
+            .unwrap();
+            addr += chunk.len() as u32;
+        }
+        sim.send_command(commands::FlashWriteCheck).unwrap();
+
+        let data = sim
+            .send_command(commands::FlashRead {
+                start_addr: 0x2000,
+                len: firmware.len() as u32,
+            })
+            .unwrap();
+        assert_eq!(data, firmware);
+
+        sim.send_command(commands::FlashXipReadStart).unwrap();
+        let sha = sim
+            .send_command(commands::FlashXipReadSha {
+                start_addr: 0x2000,
+                len: firmware.len() as u32,
+            })
+            .unwrap();
+        assert_eq!(sha, Sha256::digest(&firmware).to_vec());
+        sim.send_command(commands::FlashXipReadFinish).unwrap();
+
+        sim.send_command(commands::Reset).unwrap();
+        assert!(!sim.is_synced());
+    }
+}
@@ -1,24 +1,49 @@
-use serialport::SerialPort;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::time::{Duration, Instant};
 
-use crate::commands::{Command, Response};
+use serialport::{ClearBuffer, SerialPort};
+
+use crate::commands::{Command, Response, DEFAULT_TIMEOUT};
 use crate::error::{Error, Result};
 
+#[cfg(feature = "async")]
+pub use self::asynchronous::*;
+pub use self::net::*;
+pub use self::record::*;
+pub use self::stream::*;
+
+#[cfg(feature = "async")]
+mod asynchronous;
+mod net;
+mod record;
+mod stream;
+
 pub trait Transport {
     fn read_bytes(&mut self, n: usize) -> Result<Vec<u8>>;
 
     fn write_bytes(&mut self, buf: &[u8]) -> Result<()>;
 
+    /// Timeout for subsequent reads
+    fn set_timeout(&mut self, _timeout: Duration) -> Result<()> {
+        Ok(())
+    }
+
     fn send_command<C: Command>(&mut self, cmd: C) -> Result<C::Response> {
+        self.send_command_with_progress(cmd, &mut |_| {})
+    }
+
+    /// Like [`Transport::send_command`], `progress` is called with the time
+    /// elapsed on every `PD` ack of a long running command
+    fn send_command_with_progress<C: Command>(
+        &mut self,
+        cmd: C,
+        progress: &mut dyn FnMut(Duration),
+    ) -> Result<C::Response> {
         let raw = cmd.to_raw();
+        log::debug!("=> {}", cmd.describe());
         self.write_bytes(&raw)?;
-        let ack = self.read_bytes(2)?;
-        if ack == b"FL" {
-            let code = self.read_u16()?;
-            return Err(Error::Code(code));
-        }
-        if ack != b"OK" {
-            return Err(Error::Custom(format!("ack != OK {:?}", ack)));
-        }
+        self.read_ack_with_progress(cmd.timeout(), progress)?;
         if C::Response::size_hint() == Some(0) {
             return Ok(C::Response::from_raw(&[])?);
         }
@@ -31,6 +56,47 @@ pub trait Transport {
         }
     }
 
+    /// Wait for the final `OK` or `FL` of a command, within `timeout`.
+    ///
+    /// `PD` (pending) acks sent during long operations are keep-alives. On
+    /// return the read timeout is left at what remains of the deadline, so the
+    /// response payload is bound by it too.
+    fn read_ack(&mut self, timeout: Duration) -> Result<()> {
+        self.read_ack_with_progress(timeout, &mut |_| {})
+    }
+
+    /// [`Transport::read_ack`], passing the time elapsed to `progress` on each `PD`
+    fn read_ack_with_progress(
+        &mut self,
+        timeout: Duration,
+        progress: &mut dyn FnMut(Duration),
+    ) -> Result<()> {
+        let start = Instant::now();
+        let deadline = start + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(Error::Timeout);
+            }
+            self.set_timeout(remaining)?;
+            let ack = self.read_bytes(2)?;
+            match &ack[..] {
+                b"OK" => return Ok(()),
+                b"FL" => {
+                    let code = self.read_u16()?;
+                    log::debug!("<= FL {:04x}", code);
+                    return Err(Error::Code(code.into()));
+                }
+                b"PD" => {
+                    log::debug!("<= PD, pending");
+                    progress(start.elapsed());
+                    continue;
+                }
+                _ => return Err(Error::Custom(format!("ack != OK {:?}", ack))),
+            }
+        }
+    }
+
     /// len for UART
     fn read_u16(&mut self) -> Result<u16> {
         let buf = self.read_bytes(2)?;
@@ -45,51 +111,148 @@ pub trait Transport {
 
     fn call_raw_no_resp(&mut self, cmd: &[u8]) -> Result<()> {
         self.write_bytes(cmd)?;
-        let ack = self.read_bytes(2)?;
-        if ack == b"FL" {
-            let code = self.read_u16()?;
-            return Err(Error::Code(code));
-        }
-        if ack != b"OK" {
-            return Err(Error::Custom(format!("ack != OK {:?}", ack)));
-        }
-        Ok(())
+        self.read_ack(DEFAULT_TIMEOUT)
     }
 
     fn call_raw_cmd(&mut self, cmd: &[u8]) -> Result<Vec<u8>> {
         self.write_bytes(cmd)?;
-        let ack = self.read_bytes(2)?;
-        if ack == b"FL" {
-            let code = self.read_u16()?;
-            return Err(Error::Code(code));
-        }
-        if ack != b"OK" {
-            return Err(Error::Custom(format!("ack != OK {:?}", ack)));
-        }
+        self.read_ack(DEFAULT_TIMEOUT)?;
         let len_payload = self.read_u16()?;
-        println!("payload => {}", len_payload);
+        log::debug!("<= payload {} bytes", len_payload);
         let payload = self.read_bytes(len_payload as usize)?;
         Ok(payload)
     }
 }
 
+/// Line control of a real (or remote) UART, not needed to speak the protocol
+pub trait SerialControl {
+    /// Reconfigure the host side UART speed
+    fn set_baud_rate(&mut self, baud_rate: u32) -> Result<()>;
+
+    /// Whether [`SerialControl::set_baud_rate`] works, a raw TCP bridge
+    /// has its speed fixed on the far end
+    fn can_set_baud_rate(&self) -> bool {
+        true
+    }
+
+    /// Drive the DTR modem control line
+    fn set_dtr(&mut self, _level: bool) -> Result<()> {
+        Err(Error::Custom("DTR not supported by transport".to_string()))
+    }
+
+    /// Drive the RTS modem control line
+    fn set_rts(&mut self, _level: bool) -> Result<()> {
+        Err(Error::Custom("RTS not supported by transport".to_string()))
+    }
+
+    /// Discard any pending bytes not read yet
+    fn clear_input(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
 impl Transport for Box<dyn SerialPort> {
+    /// Read exactly `n` bytes, USB-serial adapters may split them over several reads
     fn read_bytes(&mut self, n: usize) -> Result<Vec<u8>> {
         let mut buf = vec![0u8; n];
-        let nread = self.read(&mut buf[..])?;
-        if nread != n {
-            return Err(Error::Custom(format!("read_bytes: nread != n")));
+        let deadline = Instant::now() + self.timeout();
+        let mut nread = 0;
+        while nread < n {
+            match self.read(&mut buf[nread..]) {
+                Ok(k) => nread += k,
+                Err(e)
+                    if e.kind() == io::ErrorKind::TimedOut
+                        || e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+            if nread < n && Instant::now() >= deadline {
+                return Err(Error::Timeout);
+            }
         }
-        println!("D: read {} => {:02x?}", n, buf);
+        log::trace!("read {} bytes\n{}", n, HexDump(&buf));
         Ok(buf)
     }
 
     fn write_bytes(&mut self, buf: &[u8]) -> Result<()> {
-        let n = self.write(buf)?;
-        if n != buf.len() {
-            return Err(Error::Custom("write_bytes: n != buf.len()".to_string()));
+        self.write_all(buf)?;
+        self.flush()?;
+        log::trace!("write {} bytes\n{}", buf.len(), HexDump(buf));
+        Ok(())
+    }
+
+    fn set_timeout(&mut self, timeout: Duration) -> Result<()> {
+        SerialPort::set_timeout(self.as_mut(), timeout)?;
+        Ok(())
+    }
+}
+
+impl SerialControl for Box<dyn SerialPort> {
+    fn clear_input(&mut self) -> Result<()> {
+        self.clear(ClearBuffer::Input)?;
+        Ok(())
+    }
+
+    fn set_baud_rate(&mut self, baud_rate: u32) -> Result<()> {
+        SerialPort::set_baud_rate(self.as_mut(), baud_rate)?;
+        Ok(())
+    }
+
+    fn set_dtr(&mut self, level: bool) -> Result<()> {
+        self.write_data_terminal_ready(level)?;
+        Ok(())
+    }
+
+    fn set_rts(&mut self, level: bool) -> Result<()> {
+        self.write_request_to_send(level)?;
+        Ok(())
+    }
+}
+
+/// Classic hex dump, 16 bytes per line with offset and ASCII column
+pub struct HexDump<'a>(pub &'a [u8]);
+
+impl fmt::Display for HexDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, line) in self.0.chunks(16).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{:08x}  ", i * 16)?;
+            for j in 0..16 {
+                match line.get(j) {
+                    Some(b) => write!(f, "{:02x} ", b)?,
+                    None => write!(f, "   ")?,
+                }
+                if j == 7 {
+                    write!(f, " ")?;
+                }
+            }
+            write!(f, " |")?;
+            for &b in line {
+                let c = if b.is_ascii_graphic() || b == b' ' {
+                    b as char
+                } else {
+                    '.'
+                };
+                write!(f, "{}", c)?;
+            }
+            write!(f, "|")?;
         }
-        println!("D: write {} => {}", n, hex::encode(&buf));
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_dump() {
+        let dump = HexDump(b"OK\x00\x10BFNP0123456789abcdef").to_string();
+        assert_eq!(
+            dump,
+            "00000000  4f 4b 00 10 42 46 4e 50  30 31 32 33 34 35 36 37  |OK..BFNP01234567|\n\
+             00000010  38 39 61 62 63 64 65 66                           |89abcdef|"
+        );
+    }
+}
@@ -0,0 +1,314 @@
+//! Async flavour of [`Transport`](super::Transport) on tokio, enabled by the
+//! `async` feature.
+//!
+//! Dropping a future (e.g. by `tokio::time::timeout` or `select!`) leaves the
+//! transport usable: bytes received by a dropped read stay buffered, the rest
+//! of a frame dropped halfway through writing is sent before anything else so
+//! the device never sees a torn frame, and a command dropped before its
+//! response arrived makes the next command discard the late response first.
+//! Whether the dropped command ran on the device is unknown.
+
+use std::time::Duration;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::time::{self, Instant};
+use tokio_serial::{SerialPort, SerialPortBuilderExt, SerialStream};
+
+use super::HexDump;
+use crate::commands::{Command, Response};
+use crate::error::{Error, Result};
+use crate::session::{sync_len, SyncOptions};
+
+/// How long the line must stay quiet before stale input counts as drained
+const QUIET_TIME: Duration = Duration::from_millis(20);
+
+pub struct AsyncTransport<S> {
+    inner: S,
+    /// received bytes not consumed yet
+    rx: Vec<u8>,
+    /// rest of a frame whose write was dropped
+    tx: Vec<u8>,
+    /// a command was dropped mid-flight, its response may still arrive
+    interrupted: bool,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> AsyncTransport<S> {
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            rx: vec![],
+            tx: vec![],
+            interrupted: false,
+        }
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /// Read exactly `n` bytes, partial data survives cancellation
+    pub async fn read_bytes(&mut self, n: usize) -> Result<Vec<u8>> {
+        while self.rx.len() < n {
+            if self.inner.read_buf(&mut self.rx).await? == 0 {
+                return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+            }
+        }
+        let buf: Vec<u8> = self.rx.drain(..n).collect();
+        log::trace!("read {} bytes\n{}", n, HexDump(&buf));
+        Ok(buf)
+    }
+
+    /// Write all of `buf`, after what is left of a dropped write
+    pub async fn write_bytes(&mut self, buf: &[u8]) -> Result<()> {
+        log::trace!("write {} bytes\n{}", buf.len(), HexDump(buf));
+        self.tx.extend_from_slice(buf);
+        self.finish_write().await
+    }
+
+    /// Send what is left of a dropped write, bytes leave `tx` once written
+    async fn finish_write(&mut self) -> Result<()> {
+        while !self.tx.is_empty() {
+            let n = self.inner.write(&self.tx).await?;
+            if n == 0 {
+                return Err(std::io::Error::from(std::io::ErrorKind::WriteZero).into());
+            }
+            self.tx.drain(..n);
+        }
+        self.inner.flush().await?;
+        Ok(())
+    }
+
+    /// Drop buffered input and whatever arrives until the line goes quiet
+    pub async fn discard_input(&mut self) -> Result<()> {
+        while let Ok(n) = time::timeout(QUIET_TIME, self.inner.read_buf(&mut self.rx)).await {
+            if n? == 0 {
+                break;
+            }
+            self.rx.clear();
+        }
+        self.rx.clear();
+        Ok(())
+    }
+
+    /// Run the UART sync, returns once the device answered `OK`
+    pub async fn sync(&mut self, options: &SyncOptions) -> Result<()> {
+        let sync_bytes = vec![0x55_u8; sync_len(options.baud_rate)];
+        self.finish_write().await?;
+        self.discard_input().await?;
+
+        for attempt in 0..=options.retries {
+            self.write_bytes(&sync_bytes).await?;
+            match time::timeout(options.timeout, self.read_sync_reply()).await {
+                Ok(Ok(())) => {
+                    self.interrupted = false;
+                    return Ok(());
+                }
+                Ok(Err(e)) => return Err(e),
+                Err(_) => log::debug!("sync attempt {}: {}", attempt, Error::Timeout),
+            }
+            time::sleep(QUIET_TIME).await;
+        }
+        Err(Error::Sync(options.retries + 1))
+    }
+
+    async fn read_sync_reply(&mut self) -> Result<()> {
+        let mut last = [0u8; 2];
+        loop {
+            let b = self.read_bytes(1).await?;
+            last = [last[1], b[0]];
+            if &last == b"OK" {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Send `cmd` and wait for its response within [`Command::timeout`]
+    pub async fn send_command<C: Command>(&mut self, cmd: C) -> Result<C::Response> {
+        if self.interrupted {
+            log::debug!("discarding the response of an interrupted command");
+            self.finish_write().await?;
+            self.discard_input().await?;
+        }
+        let deadline = Instant::now() + cmd.timeout();
+        log::debug!("=> {}", cmd.describe());
+
+        self.interrupted = true;
+        let ret = match time::timeout_at(deadline, self.exchange::<C>(&cmd.to_raw())).await {
+            Ok(ret) => ret,
+            Err(_) => return Err(Error::Timeout),
+        };
+        // only a finished exchange leaves the line in a known state
+        if !matches!(ret, Err(Error::Io(_))) {
+            self.interrupted = false;
+        }
+        ret
+    }
+
+    async fn exchange<C: Command>(&mut self, raw: &[u8]) -> Result<C::Response> {
+        self.write_bytes(raw).await?;
+        self.read_ack().await?;
+        if C::Response::size_hint() == Some(0) {
+            return C::Response::from_raw(&[]);
+        }
+        let len = self.read_bytes(2).await?;
+        let len = u16::from_le_bytes([len[0], len[1]]) as usize;
+        let payload = self.read_bytes(len).await?;
+        C::Response::from_raw(&payload)
+    }
+
+    async fn read_ack(&mut self) -> Result<()> {
+        loop {
+            let ack = self.read_bytes(2).await?;
+            match &ack[..] {
+                b"OK" => return Ok(()),
+                b"FL" => {
+                    let code = self.read_bytes(2).await?;
+                    let code = u16::from_le_bytes([code[0], code[1]]);
+                    log::debug!("<= FL {:04x}", code);
+                    return Err(Error::Code(code.into()));
+                }
+                b"PD" => log::debug!("<= PD, pending"),
+                _ => return Err(Error::Custom(format!("ack != OK {:?}", ack))),
+            }
+        }
+    }
+}
+
+impl AsyncTransport<SerialStream> {
+    /// Open a serial port registered with the current tokio runtime
+    pub fn open(path: &str, baud_rate: u32) -> Result<Self> {
+        let port = tokio_serial::new(path, baud_rate).open_native_async()?;
+        Ok(Self::new(port))
+    }
+
+    /// Reconfigure the host side UART speed
+    pub fn set_baud_rate(&mut self, baud_rate: u32) -> Result<()> {
+        self.inner.set_baud_rate(baud_rate)?;
+        Ok(())
+    }
+
+    /// Drive the DTR modem control line
+    pub fn set_dtr(&mut self, level: bool) -> Result<()> {
+        self.inner.write_data_terminal_ready(level)?;
+        Ok(())
+    }
+
+    /// Drive the RTS modem control line
+    pub fn set_rts(&mut self, level: bool) -> Result<()> {
+        self.inner.write_request_to_send(level)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{duplex, DuplexStream};
+
+    use super::*;
+    use crate::commands;
+    use crate::sim::Simulator;
+    use crate::transport::Transport;
+
+    /// Serve a simulated chip on the far end of `stream` until it closes
+    fn spawn_device(mut stream: DuplexStream) {
+        tokio::spawn(async move {
+            let mut sim = Simulator::new();
+            let mut buf = [0u8; 4096];
+            while let Ok(n @ 1..) = stream.read(&mut buf).await {
+                sim.write_bytes(&buf[..n]).unwrap();
+                let reply = sim.read_bytes(sim.available()).unwrap();
+                if stream.write_all(&reply).await.is_err() {
+                    return;
+                }
+            }
+        });
+    }
+
+    #[tokio::test]
+    async fn send_command() {
+        let (host, device) = duplex(8192);
+        spawn_device(device);
+        let mut transport = AsyncTransport::new(host);
+        transport.sync(&SyncOptions::default()).await.unwrap();
+
+        let chip_id = transport.send_command(commands::GetChipId).await.unwrap();
+        assert_eq!(chip_id, "CHIPWB03A00_BL\0\0");
+        let data = transport
+            .send_command(commands::FlashRead {
+                start_addr: 0,
+                len: 64,
+            })
+            .await
+            .unwrap();
+        assert_eq!(data, vec![0xff; 64]);
+    }
+
+    #[tokio::test]
+    async fn cancelled_read_keeps_data() {
+        let (host, mut device) = duplex(64);
+        let mut transport = AsyncTransport::new(host);
+        device.write_all(b"O").await.unwrap();
+        let read = time::timeout(Duration::from_millis(10), transport.read_bytes(2));
+        assert!(read.await.is_err());
+        device.write_all(b"K").await.unwrap();
+        assert_eq!(transport.read_bytes(2).await.unwrap(), b"OK");
+    }
+
+    #[tokio::test]
+    async fn cancelled_command_discards_late_response() {
+        let (host, mut device) = duplex(64);
+        let mut transport = AsyncTransport::new(host);
+
+        let cmd = time::timeout(
+            Duration::from_millis(10),
+            transport.send_command(commands::GetChipId),
+        );
+        assert!(cmd.await.is_err());
+        // the response shows up after the caller gave up
+        device.write_all(b"OK\x04\x00late").await.unwrap();
+
+        let device = async {
+            let mut frame = [0u8; 8];
+            device.read_exact(&mut frame).await.unwrap();
+            assert_eq!(frame, [0x05, 0x00, 0x00, 0x00, 0x05, 0x00, 0x00, 0x00]);
+            device.write_all(b"OK\x04\x00next").await.unwrap();
+        };
+        let (chip_id, ()) = tokio::join!(transport.send_command(commands::GetChipId), device);
+        assert_eq!(chip_id.unwrap(), "next");
+    }
+
+    #[tokio::test]
+    async fn cancelled_write_completes_frame() {
+        // the device doesn't read, the pipe fills up mid-frame
+        let (host, mut device) = duplex(16);
+        let mut transport = AsyncTransport::new(host);
+        let write = commands::FlashWrite {
+            start_addr: 0x2000,
+            data: vec![0xa5; 64],
+        };
+        let frame = write.to_raw();
+        let cmd = time::timeout(Duration::from_millis(10), transport.send_command(write));
+        assert!(cmd.await.is_err());
+
+        let device = async {
+            let mut buf = vec![0u8; frame.len()];
+            device.read_exact(&mut buf).await.unwrap();
+            assert_eq!(buf, frame);
+            device.write_all(b"OK").await.unwrap();
+            let mut buf = [0u8; 4];
+            device.read_exact(&mut buf).await.unwrap();
+            assert_eq!(buf, [0x05, 0x00, 0x00, 0x00]);
+            device.write_all(b"OK\x04\x00next").await.unwrap();
+        };
+        let (chip_id, ()) = tokio::join!(transport.send_command(commands::GetChipId), device);
+        assert_eq!(chip_id.unwrap(), "next");
+    }
+}
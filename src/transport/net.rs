@@ -0,0 +1,527 @@
+//! Serial ports exported over the network.
+//!
+//! Supports a raw TCP bridge (ser2net `raw`/`telnet` off) and RFC 2217, the
+//! telnet COM-PORT-OPTION extension, which also forwards baud rate and
+//! DTR/RTS changes to the remote UART.
+
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+use super::{SerialControl, Transport};
+use crate::error::{Error, Result};
+
+// telnet
+const IAC: u8 = 255;
+const DONT: u8 = 254;
+const DO: u8 = 253;
+const WONT: u8 = 252;
+const WILL: u8 = 251;
+const SB: u8 = 250;
+const SE: u8 = 240;
+
+const OPT_BINARY: u8 = 0;
+const OPT_SGA: u8 = 3;
+const OPT_COM_PORT: u8 = 44;
+
+// RFC 2217 client to server subcommands, server replies add 100
+const SET_BAUDRATE: u8 = 1;
+const SET_DATASIZE: u8 = 2;
+const SET_PARITY: u8 = 3;
+const SET_STOPSIZE: u8 = 4;
+const SET_CONTROL: u8 = 5;
+const PURGE_DATA: u8 = 12;
+
+const CONTROL_NO_FLOW: u8 = 1;
+const CONTROL_DTR_ON: u8 = 8;
+const CONTROL_DTR_OFF: u8 = 9;
+const CONTROL_RTS_ON: u8 = 11;
+const CONTROL_RTS_OFF: u8 = 12;
+const PURGE_RX: u8 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TelnetEvent {
+    Data(u8),
+    /// WILL/WONT/DO/DONT and the option
+    Negotiate(u8, u8),
+    /// Content between `IAC SB` and `IAC SE`, unescaped
+    Subnegotiation(Vec<u8>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DecodeState {
+    Data,
+    Iac,
+    Negotiate(u8),
+    Sb,
+    SbIac,
+}
+
+/// Byte-at-a-time telnet stream decoder
+#[derive(Debug)]
+pub struct TelnetDecoder {
+    state: DecodeState,
+    sb: Vec<u8>,
+}
+
+impl Default for TelnetDecoder {
+    fn default() -> Self {
+        Self {
+            state: DecodeState::Data,
+            sb: vec![],
+        }
+    }
+}
+
+impl TelnetDecoder {
+    pub fn feed(&mut self, b: u8) -> Option<TelnetEvent> {
+        match self.state {
+            DecodeState::Data => {
+                if b == IAC {
+                    self.state = DecodeState::Iac;
+                    None
+                } else {
+                    Some(TelnetEvent::Data(b))
+                }
+            }
+            DecodeState::Iac => {
+                self.state = DecodeState::Data;
+                match b {
+                    IAC => Some(TelnetEvent::Data(IAC)),
+                    WILL | WONT | DO | DONT => {
+                        self.state = DecodeState::Negotiate(b);
+                        None
+                    }
+                    SB => {
+                        self.sb.clear();
+                        self.state = DecodeState::Sb;
+                        None
+                    }
+                    // NOP, GA and friends
+                    _ => None,
+                }
+            }
+            DecodeState::Negotiate(cmd) => {
+                self.state = DecodeState::Data;
+                Some(TelnetEvent::Negotiate(cmd, b))
+            }
+            DecodeState::Sb => {
+                if b == IAC {
+                    self.state = DecodeState::SbIac;
+                } else {
+                    self.sb.push(b);
+                }
+                None
+            }
+            DecodeState::SbIac => match b {
+                IAC => {
+                    self.sb.push(IAC);
+                    self.state = DecodeState::Sb;
+                    None
+                }
+                SE => {
+                    self.state = DecodeState::Data;
+                    Some(TelnetEvent::Subnegotiation(std::mem::take(&mut self.sb)))
+                }
+                _ => {
+                    // malformed, drop the subnegotiation
+                    self.state = DecodeState::Data;
+                    None
+                }
+            },
+        }
+    }
+}
+
+/// Double every IAC in `data`
+pub fn telnet_escape(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len());
+    for &b in data {
+        out.push(b);
+        if b == IAC {
+            out.push(IAC);
+        }
+    }
+    out
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetProtocol {
+    /// Plain byte stream, line settings fixed on the server
+    Raw,
+    /// RFC 2217
+    Rfc2217,
+}
+
+pub struct TcpTransport {
+    stream: TcpStream,
+    protocol: NetProtocol,
+    timeout: Duration,
+    decoder: TelnetDecoder,
+    rx: VecDeque<u8>,
+    /// Options we asked for with WILL / DO, their answers need no reply
+    offered: Vec<(u8, u8)>,
+}
+
+impl TcpTransport {
+    pub fn connect<A: ToSocketAddrs>(addr: A, protocol: NetProtocol) -> Result<Self> {
+        let stream = TcpStream::connect(addr)?;
+        stream.set_nodelay(true)?;
+        let mut transport = Self {
+            stream,
+            protocol,
+            timeout: Duration::from_secs(2),
+            decoder: TelnetDecoder::default(),
+            rx: VecDeque::new(),
+            offered: vec![],
+        };
+        if protocol == NetProtocol::Rfc2217 {
+            transport.negotiate()?;
+        }
+        Ok(transport)
+    }
+
+    /// ser2net style raw TCP bridge
+    pub fn connect_raw<A: ToSocketAddrs>(addr: A) -> Result<Self> {
+        Self::connect(addr, NetProtocol::Raw)
+    }
+
+    pub fn connect_rfc2217<A: ToSocketAddrs>(addr: A) -> Result<Self> {
+        Self::connect(addr, NetProtocol::Rfc2217)
+    }
+
+    pub fn protocol(&self) -> NetProtocol {
+        self.protocol
+    }
+
+    fn negotiate(&mut self) -> Result<()> {
+        let mut out = vec![];
+        for (cmd, opt) in [
+            (WILL, OPT_BINARY),
+            (DO, OPT_BINARY),
+            (WILL, OPT_SGA),
+            (DO, OPT_SGA),
+            (WILL, OPT_COM_PORT),
+        ] {
+            out.extend_from_slice(&[IAC, cmd, opt]);
+            self.offered.push((cmd, opt));
+        }
+        self.stream.write_all(&out)?;
+
+        // 8N1 and no flow control, the baud rate is set by the session
+        self.com_port_command(SET_DATASIZE, &[8])?;
+        self.com_port_command(SET_PARITY, &[1])?;
+        self.com_port_command(SET_STOPSIZE, &[1])?;
+        self.com_port_command(SET_CONTROL, &[CONTROL_NO_FLOW])?;
+        Ok(())
+    }
+
+    fn com_port_command(&mut self, subcommand: u8, value: &[u8]) -> Result<()> {
+        let mut out = vec![IAC, SB, OPT_COM_PORT, subcommand];
+        out.extend(telnet_escape(value));
+        out.extend_from_slice(&[IAC, SE]);
+        log::debug!("rfc2217 => subcommand {} {:02x?}", subcommand, value);
+        self.stream.write_all(&out)?;
+        Ok(())
+    }
+
+    fn set_control(&mut self, control: u8) -> Result<()> {
+        match self.protocol {
+            NetProtocol::Rfc2217 => self.com_port_command(SET_CONTROL, &[control]),
+            NetProtocol::Raw => Err(Error::Custom(
+                "modem control lines need an RFC 2217 server".to_string(),
+            )),
+        }
+    }
+
+    fn handle_event(&mut self, event: TelnetEvent) -> Result<()> {
+        match event {
+            TelnetEvent::Data(b) => self.rx.push_back(b),
+            TelnetEvent::Negotiate(cmd, opt) => {
+                let (ours, refuse) = match cmd {
+                    DO | DONT => (WILL, WONT),
+                    _ => (DO, DONT),
+                };
+                if !self.offered.contains(&(ours, opt)) && (cmd == DO || cmd == WILL) {
+                    self.stream.write_all(&[IAC, refuse, opt])?;
+                }
+            }
+            TelnetEvent::Subnegotiation(sb) => {
+                log::debug!("rfc2217 <= {:02x?}", sb);
+            }
+        }
+        Ok(())
+    }
+
+    /// Move whatever the socket has into `rx`, waiting at most until `deadline`
+    fn fill(&mut self, deadline: Instant) -> Result<()> {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(Error::Timeout);
+        }
+        self.stream.set_read_timeout(Some(remaining))?;
+        let mut buf = [0u8; 1024];
+        let n = match self.stream.read(&mut buf) {
+            Ok(0) => {
+                return Err(Error::Io(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "connection closed by server",
+                )))
+            }
+            Ok(n) => n,
+            Err(e)
+                if e.kind() == io::ErrorKind::WouldBlock
+                    || e.kind() == io::ErrorKind::TimedOut
+                    || e.kind() == io::ErrorKind::Interrupted =>
+            {
+                return Ok(())
+            }
+            Err(e) => return Err(e.into()),
+        };
+        match self.protocol {
+            NetProtocol::Raw => self.rx.extend(&buf[..n]),
+            NetProtocol::Rfc2217 => {
+                for &b in &buf[..n] {
+                    if let Some(event) = self.decoder.feed(b) {
+                        self.handle_event(event)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Transport for TcpTransport {
+    fn read_bytes(&mut self, n: usize) -> Result<Vec<u8>> {
+        let deadline = Instant::now() + self.timeout;
+        while self.rx.len() < n {
+            self.fill(deadline)?;
+        }
+        Ok(self.rx.drain(..n).collect())
+    }
+
+    fn write_bytes(&mut self, buf: &[u8]) -> Result<()> {
+        match self.protocol {
+            NetProtocol::Raw => self.stream.write_all(buf)?,
+            NetProtocol::Rfc2217 => self.stream.write_all(&telnet_escape(buf))?,
+        }
+        Ok(())
+    }
+
+    fn set_timeout(&mut self, timeout: Duration) -> Result<()> {
+        self.timeout = timeout;
+        Ok(())
+    }
+}
+
+impl SerialControl for TcpTransport {
+    fn clear_input(&mut self) -> Result<()> {
+        if self.protocol == NetProtocol::Rfc2217 {
+            self.com_port_command(PURGE_DATA, &[PURGE_RX])?;
+        }
+        // drop what is already on the way
+        let deadline = Instant::now() + Duration::from_millis(10);
+        while Instant::now() < deadline {
+            match self.fill(deadline) {
+                Ok(()) | Err(Error::Timeout) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        self.rx.clear();
+        Ok(())
+    }
+
+    fn can_set_baud_rate(&self) -> bool {
+        self.protocol == NetProtocol::Rfc2217
+    }
+
+    fn set_baud_rate(&mut self, baud_rate: u32) -> Result<()> {
+        match self.protocol {
+            NetProtocol::Rfc2217 => self.com_port_command(SET_BAUDRATE, &baud_rate.to_be_bytes()),
+            NetProtocol::Raw => Err(Error::Custom(
+                "baud rate change needs an RFC 2217 server".to_string(),
+            )),
+        }
+    }
+
+    fn set_dtr(&mut self, level: bool) -> Result<()> {
+        self.set_control(if level {
+            CONTROL_DTR_ON
+        } else {
+            CONTROL_DTR_OFF
+        })
+    }
+
+    fn set_rts(&mut self, level: bool) -> Result<()> {
+        self.set_control(if level {
+            CONTROL_RTS_ON
+        } else {
+            CONTROL_RTS_OFF
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    use super::*;
+    use crate::commands;
+    use crate::session::{Session, SyncOptions};
+    use crate::sim::Simulator;
+
+    /// What the loopback bridge saw besides data
+    #[derive(Debug, Default)]
+    struct BridgeLog {
+        baud_rates: Vec<u32>,
+        controls: Vec<u8>,
+    }
+
+    /// ser2net stand-in, one client, backed by a simulator
+    fn spawn_bridge(protocol: NetProtocol) -> (String, JoinHandle<BridgeLog>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut sim = Simulator::new();
+            let mut decoder = TelnetDecoder::default();
+            let mut log = BridgeLog::default();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = match stream.read(&mut buf) {
+                    Ok(0) | Err(_) => return log,
+                    Ok(n) => n,
+                };
+                let mut data = vec![];
+                for &b in &buf[..n] {
+                    if protocol == NetProtocol::Raw {
+                        data.push(b);
+                        continue;
+                    }
+                    match decoder.feed(b) {
+                        Some(TelnetEvent::Data(b)) => data.push(b),
+                        Some(TelnetEvent::Subnegotiation(sb)) if sb[0] == OPT_COM_PORT => {
+                            match sb[1] {
+                                SET_BAUDRATE => {
+                                    let rate = u32::from_be_bytes(sb[2..6].try_into().unwrap());
+                                    sim.set_baud_rate(rate).unwrap();
+                                    log.baud_rates.push(rate);
+                                }
+                                SET_CONTROL => log.controls.push(sb[2]),
+                                _ => {}
+                            }
+                            // acknowledge with the server side subcommand
+                            let mut ack = vec![IAC, SB, OPT_COM_PORT, sb[1] + 100];
+                            ack.extend(telnet_escape(&sb[2..]));
+                            ack.extend_from_slice(&[IAC, SE]);
+                            stream.write_all(&ack).unwrap();
+                        }
+                        Some(TelnetEvent::Negotiate(WILL, opt)) => {
+                            stream.write_all(&[IAC, DO, opt]).unwrap();
+                        }
+                        Some(TelnetEvent::Negotiate(DO, opt)) => {
+                            stream.write_all(&[IAC, WILL, opt]).unwrap();
+                        }
+                        _ => {}
+                    }
+                }
+                sim.write_bytes(&data).unwrap();
+                let out = sim.read_bytes(sim.available()).unwrap();
+                let out = match protocol {
+                    NetProtocol::Raw => out,
+                    NetProtocol::Rfc2217 => telnet_escape(&out),
+                };
+                stream.write_all(&out).unwrap();
+            }
+        });
+        (addr, handle)
+    }
+
+    #[test]
+    fn telnet_decode() {
+        let mut decoder = TelnetDecoder::default();
+        let stream = [
+            0x4f,
+            IAC,
+            IAC,
+            IAC,
+            WILL,
+            OPT_COM_PORT,
+            IAC,
+            SB,
+            OPT_COM_PORT,
+            101,
+            0,
+            0,
+            IAC,
+            IAC,
+            0,
+            IAC,
+            SE,
+            0x4b,
+        ];
+        let events: Vec<_> = stream.iter().filter_map(|&b| decoder.feed(b)).collect();
+        assert_eq!(
+            events,
+            vec![
+                TelnetEvent::Data(0x4f),
+                TelnetEvent::Data(IAC),
+                TelnetEvent::Negotiate(WILL, OPT_COM_PORT),
+                TelnetEvent::Subnegotiation(vec![OPT_COM_PORT, 101, 0, 0, IAC, 0]),
+                TelnetEvent::Data(0x4b),
+            ]
+        );
+    }
+
+    #[test]
+    fn raw_bridge() {
+        let (addr, bridge) = spawn_bridge(NetProtocol::Raw);
+        let transport = TcpTransport::connect_raw(&addr).unwrap();
+        let mut session = Session::connect(transport, &SyncOptions::default()).unwrap();
+        let data = session
+            .send_command(commands::FlashRead {
+                start_addr: 0,
+                len: 300,
+            })
+            .unwrap();
+        assert_eq!(data, vec![0xff; 300]);
+        drop(session);
+        bridge.join().unwrap();
+    }
+
+    #[test]
+    fn rfc2217_bridge() {
+        let (addr, bridge) = spawn_bridge(NetProtocol::Rfc2217);
+        let mut transport = TcpTransport::connect_rfc2217(&addr).unwrap();
+        transport.set_dtr(true).unwrap();
+        transport.set_rts(false).unwrap();
+        let mut session = Session::connect(transport, &SyncOptions::default()).unwrap();
+        assert_eq!(session.switch_baud_rate(1_000_000).unwrap(), 1_000_000);
+
+        // payload containing 0xff must survive IAC escaping
+        session
+            .send_command(commands::FlashWrite {
+                start_addr: 0x1000,
+                data: vec![0xff, 0x00, 0xff, 0xff, 0x12],
+            })
+            .unwrap();
+        let data = session
+            .send_command(commands::FlashRead {
+                start_addr: 0x1000,
+                len: 5,
+            })
+            .unwrap();
+        assert_eq!(data, vec![0xff, 0x00, 0xff, 0xff, 0x12]);
+        drop(session);
+
+        let log = bridge.join().unwrap();
+        assert_eq!(log.baud_rates, vec![1_000_000]);
+        assert_eq!(
+            log.controls,
+            vec![CONTROL_NO_FLOW, CONTROL_DTR_ON, CONTROL_RTS_OFF]
+        );
+    }
+}
@@ -0,0 +1,530 @@
+//! Wire-level session capture and replay.
+//!
+//! A capture is a text file, one event per line:
+//!
+//! ```text
+//! # bl capture v2
+//!      11907 C
+//!      12034 W 5555555555
+//!      18771 R 4f4b
+//!    2018012 T
+//!    2520400 B 2000000
+//! ```
+//!
+//! The first column is microseconds since the capture started, then `W` for
+//! bytes written to the device, `R` for bytes read from it, `T` for a read
+//! timeout and `E` followed by a message for any other error. Line control
+//! is recorded as `B` with the new baud rate, `C` for dropped input and
+//! `DTR`/`RTS` with the level as `0` or `1`; a failed line control call is
+//! recorded as its `E` alone. Backslashes, CR and LF in messages are escaped
+//! as `\\`, `\r` and `\n`.
+//!
+//! Version 1 captures have no line control events, replaying them accepts
+//! any.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use super::{SerialControl, Transport};
+use crate::error::{Error, Result};
+
+const HEADER: &str = "# bl capture v2";
+const HEADER_V1: &str = "# bl capture v1";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    Write(Vec<u8>),
+    Read(Vec<u8>),
+    Timeout,
+    Error(String),
+    BaudRate(u32),
+    ClearInput,
+    Dtr(bool),
+    Rts(bool),
+}
+
+fn escape(msg: &str) -> String {
+    let mut out = String::with_capacity(msg.len());
+    for c in msg.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\r' => out.push_str("\\r"),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn unescape(msg: &str) -> Option<String> {
+    let mut out = String::with_capacity(msg.len());
+    let mut chars = msg.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next()? {
+            '\\' => out.push('\\'),
+            'r' => out.push('\r'),
+            'n' => out.push('\n'),
+            _ => return None,
+        }
+    }
+    Some(out)
+}
+
+/// Transport wrapper that logs every frame to `out`
+pub struct Recorder<T, W: Write = BufWriter<File>> {
+    inner: T,
+    out: W,
+    start: Instant,
+}
+
+impl<T: Transport> Recorder<T> {
+    pub fn create<P: AsRef<Path>>(inner: T, path: P) -> Result<Self> {
+        Self::new(inner, BufWriter::new(File::create(path)?))
+    }
+}
+
+impl<T: Transport, W: Write> Recorder<T, W> {
+    pub fn new(inner: T, mut out: W) -> Result<Self> {
+        writeln!(out, "{}", HEADER)?;
+        Ok(Self {
+            inner,
+            out,
+            start: Instant::now(),
+        })
+    }
+
+    fn record(&mut self, event: &Event) -> Result<()> {
+        let ts = self.start.elapsed().as_micros();
+        match event {
+            Event::Write(data) => writeln!(self.out, "{:>10} W {}", ts, hex::encode(data))?,
+            Event::Read(data) => writeln!(self.out, "{:>10} R {}", ts, hex::encode(data))?,
+            Event::Timeout => writeln!(self.out, "{:>10} T", ts)?,
+            Event::Error(msg) => writeln!(self.out, "{:>10} E {}", ts, escape(msg))?,
+            Event::BaudRate(rate) => writeln!(self.out, "{:>10} B {}", ts, rate)?,
+            Event::ClearInput => writeln!(self.out, "{:>10} C", ts)?,
+            Event::Dtr(level) => writeln!(self.out, "{:>10} DTR {}", ts, *level as u8)?,
+            Event::Rts(level) => writeln!(self.out, "{:>10} RTS {}", ts, *level as u8)?,
+        }
+        self.out.flush()?;
+        Ok(())
+    }
+
+    /// Record the outcome of a line control call
+    fn record_control(&mut self, event: Event, result: Result<()>) -> Result<()> {
+        match result {
+            Ok(()) => self.record(&event),
+            Err(e) => {
+                self.record(&Event::Error(e.to_string()))?;
+                Err(e)
+            }
+        }
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn into_parts(self) -> (T, W) {
+        (self.inner, self.out)
+    }
+}
+
+impl<T: Transport, W: Write> Transport for Recorder<T, W> {
+    fn read_bytes(&mut self, n: usize) -> Result<Vec<u8>> {
+        match self.inner.read_bytes(n) {
+            Ok(buf) => {
+                self.record(&Event::Read(buf.clone()))?;
+                Ok(buf)
+            }
+            Err(Error::Timeout) => {
+                self.record(&Event::Timeout)?;
+                Err(Error::Timeout)
+            }
+            Err(e) => {
+                self.record(&Event::Error(e.to_string()))?;
+                Err(e)
+            }
+        }
+    }
+
+    fn write_bytes(&mut self, buf: &[u8]) -> Result<()> {
+        self.record(&Event::Write(buf.to_vec()))?;
+        self.inner.write_bytes(buf)
+    }
+
+    fn set_timeout(&mut self, timeout: Duration) -> Result<()> {
+        self.inner.set_timeout(timeout)
+    }
+}
+
+impl<T: Transport + SerialControl, W: Write> SerialControl for Recorder<T, W> {
+    fn clear_input(&mut self) -> Result<()> {
+        let result = self.inner.clear_input();
+        self.record_control(Event::ClearInput, result)
+    }
+
+    fn set_baud_rate(&mut self, baud_rate: u32) -> Result<()> {
+        let result = self.inner.set_baud_rate(baud_rate);
+        self.record_control(Event::BaudRate(baud_rate), result)
+    }
+
+    fn can_set_baud_rate(&self) -> bool {
+        self.inner.can_set_baud_rate()
+    }
+
+    fn set_dtr(&mut self, level: bool) -> Result<()> {
+        let result = self.inner.set_dtr(level);
+        self.record_control(Event::Dtr(level), result)
+    }
+
+    fn set_rts(&mut self, level: bool) -> Result<()> {
+        let result = self.inner.set_rts(level);
+        self.record_control(Event::Rts(level), result)
+    }
+}
+
+/// Transport serving a recorded session back.
+///
+/// Writes and line control must match the capture, reads return the
+/// recorded bytes regardless of how the original reads were split.
+pub struct Replay {
+    events: VecDeque<Event>,
+    /// v1 captures don't record line control
+    line_control: bool,
+    /// the recording transport changed the baud rate at least once
+    baud_rate_changes: bool,
+    /// recorded device output not consumed yet
+    rx: VecDeque<u8>,
+    /// index of the next event, for error messages
+    pos: usize,
+}
+
+impl Replay {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self> {
+        let mut events = VecDeque::new();
+        let mut line_control = true;
+        for (lineno, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line == HEADER_V1 {
+                line_control = false;
+            }
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let bad_line = || Error::Custom(format!("capture line {}: {:?}", lineno + 1, line));
+            let mut parts = line.splitn(3, ' ').filter(|s| !s.is_empty());
+            let _ts = parts.next().ok_or_else(bad_line)?;
+            let kind = parts.next().ok_or_else(bad_line)?;
+            let rest = parts.next().unwrap_or("").trim();
+            let event = match kind {
+                "W" => Event::Write(hex::decode(rest).map_err(|_| bad_line())?),
+                "R" => Event::Read(hex::decode(rest).map_err(|_| bad_line())?),
+                "T" => Event::Timeout,
+                "E" => Event::Error(unescape(rest).ok_or_else(bad_line)?),
+                "B" => Event::BaudRate(rest.parse().map_err(|_| bad_line())?),
+                "C" => Event::ClearInput,
+                "DTR" | "RTS" => {
+                    let level = match rest {
+                        "0" => false,
+                        "1" => true,
+                        _ => return Err(bad_line()),
+                    };
+                    if kind == "DTR" {
+                        Event::Dtr(level)
+                    } else {
+                        Event::Rts(level)
+                    }
+                }
+                _ => return Err(bad_line()),
+            };
+            events.push_back(event);
+        }
+        let baud_rate_changes = events.iter().any(|e| matches!(e, Event::BaudRate(_)));
+        Ok(Self {
+            events,
+            line_control,
+            baud_rate_changes,
+            rx: VecDeque::new(),
+            pos: 0,
+        })
+    }
+
+    /// Whether every recorded event has been served
+    pub fn is_finished(&self) -> bool {
+        self.events.is_empty() && self.rx.is_empty()
+    }
+
+    fn next_event(&mut self) -> Option<Event> {
+        let event = self.events.pop_front();
+        if event.is_some() {
+            self.pos += 1;
+        }
+        event
+    }
+
+    /// Match a line control call against the capture
+    fn control(&mut self, event: Event) -> Result<()> {
+        if !self.line_control {
+            return Ok(());
+        }
+        loop {
+            match self.next_event() {
+                Some(Event::Read(data)) => self.rx.extend(data),
+                Some(Event::Error(msg)) => return Err(Error::Custom(format!("replayed: {}", msg))),
+                Some(recorded) if recorded == event => return Ok(()),
+                Some(recorded) => {
+                    return Err(Error::Custom(format!(
+                        "replay: line control mismatch at event {}: expected {:?}, got {:?}",
+                        self.pos, recorded, event
+                    )))
+                }
+                None => return Err(Error::Custom("replay: capture exhausted".to_string())),
+            }
+        }
+    }
+}
+
+impl Transport for Replay {
+    fn read_bytes(&mut self, n: usize) -> Result<Vec<u8>> {
+        while self.rx.len() < n {
+            match self.events.front() {
+                Some(Event::Read(_)) => {
+                    if let Some(Event::Read(data)) = self.next_event() {
+                        self.rx.extend(data);
+                    }
+                }
+                Some(Event::Timeout) => {
+                    self.next_event();
+                    return Err(Error::Timeout);
+                }
+                Some(Event::Error(_)) => {
+                    if let Some(Event::Error(msg)) = self.next_event() {
+                        return Err(Error::Custom(format!("replayed: {}", msg)));
+                    }
+                }
+                Some(_) => {
+                    return Err(Error::Custom(format!(
+                        "replay: read of {} bytes, capture expects {:?} at event {}",
+                        n,
+                        self.events.front(),
+                        self.pos
+                    )))
+                }
+                None => return Err(Error::Timeout),
+            }
+        }
+        Ok(self.rx.drain(..n).collect())
+    }
+
+    fn write_bytes(&mut self, buf: &[u8]) -> Result<()> {
+        let mut buf = buf;
+        while !buf.is_empty() {
+            match self.next_event() {
+                // recorded reads this host skipped, keep them queued
+                Some(Event::Read(data)) => self.rx.extend(data),
+                Some(Event::Write(expected)) => {
+                    let n = expected.len().min(buf.len());
+                    if expected[..n] != buf[..n] {
+                        return Err(Error::Custom(format!(
+                            "replay: write mismatch at event {}: expected {}, got {}",
+                            self.pos,
+                            hex::encode(&expected),
+                            hex::encode(&buf[..n])
+                        )));
+                    }
+                    if n < expected.len() {
+                        // the rest is matched by the next write
+                        self.events.push_front(Event::Write(expected[n..].to_vec()));
+                        self.pos -= 1;
+                    }
+                    buf = &buf[n..];
+                }
+                Some(_) => {
+                    return Err(Error::Custom(format!(
+                        "replay: unexpected write at event {}",
+                        self.pos
+                    )))
+                }
+                None => return Err(Error::Custom("replay: capture exhausted".to_string())),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SerialControl for Replay {
+    fn set_baud_rate(&mut self, baud_rate: u32) -> Result<()> {
+        self.control(Event::BaudRate(baud_rate))
+    }
+
+    /// A capture without any baud rate change is taken to come from a
+    /// transport that can't change it, so the host takes the same path
+    fn can_set_baud_rate(&self) -> bool {
+        !self.line_control || self.baud_rate_changes
+    }
+
+    fn set_dtr(&mut self, level: bool) -> Result<()> {
+        self.control(Event::Dtr(level))
+    }
+
+    fn set_rts(&mut self, level: bool) -> Result<()> {
+        self.control(Event::Rts(level))
+    }
+
+    fn clear_input(&mut self) -> Result<()> {
+        self.control(Event::ClearInput)?;
+        self.rx.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands;
+    use crate::error::ErrorCode;
+    use crate::session::{Session, SyncOptions};
+    use crate::sim::Simulator;
+
+    #[test]
+    fn record_and_replay() {
+        let recorder = Recorder::new(Simulator::new(), vec![]).unwrap();
+        let mut session = Session::connect(recorder, &SyncOptions::default()).unwrap();
+        let data = session
+            .send_command(commands::FlashRead {
+                start_addr: 0,
+                len: 16,
+            })
+            .unwrap();
+        assert_eq!(data, vec![0xff; 16]);
+        let (_, capture) = session.into_inner().into_parts();
+
+        let capture = String::from_utf8(capture).unwrap();
+        assert!(capture.starts_with(HEADER));
+
+        let replay = Replay::from_reader(capture.as_bytes()).unwrap();
+        let mut session = Session::connect(replay, &SyncOptions::default()).unwrap();
+        let replayed = session
+            .send_command(commands::FlashRead {
+                start_addr: 0,
+                len: 16,
+            })
+            .unwrap();
+        assert_eq!(replayed, data);
+        assert!(session.transport().is_finished());
+    }
+
+    #[test]
+    fn replay_reproduces_failure() {
+        fn flash_twice<T: Transport>(session: &mut Session<T>) -> Result<()> {
+            session.send_command(commands::FlashWrite {
+                start_addr: 0x2000,
+                data: vec![0x00; 16],
+            })?;
+            // not erased in between
+            session.send_command(commands::FlashWrite {
+                start_addr: 0x2000,
+                data: vec![0xff; 16],
+            })
+        }
+
+        let recorder = Recorder::new(Simulator::new(), vec![]).unwrap();
+        let mut session = Session::connect(recorder, &SyncOptions::default()).unwrap();
+        let err = flash_twice(&mut session).unwrap_err();
+        assert_eq!(err.code(), Some(ErrorCode::FlashWrite));
+        let (_, capture) = session.into_inner().into_parts();
+
+        let replay = Replay::from_reader(&capture[..]).unwrap();
+        let mut session = Session::connect(replay, &SyncOptions::default()).unwrap();
+        let err = flash_twice(&mut session).unwrap_err();
+        assert_eq!(err.code(), Some(ErrorCode::FlashWrite));
+    }
+
+    #[test]
+    fn replay_detects_diverging_host() {
+        let capture = "# bl capture v1\n 1 W 5555\n 2 R 4f4b\n 3 W 05000000\n 4 T\n";
+        let mut replay = Replay::from_reader(capture.as_bytes()).unwrap();
+        replay.write_bytes(&[0x55, 0x55]).unwrap();
+        assert_eq!(replay.read_bytes(2).unwrap(), b"OK");
+        let err = replay.write_bytes(&[0x10, 0x00, 0x00, 0x00]).unwrap_err();
+        assert!(err.to_string().contains("mismatch"));
+    }
+
+    #[test]
+    fn error_message_escaped() {
+        let mut recorder = Recorder::new(Simulator::new(), vec![]).unwrap();
+        recorder
+            .record(&Event::Error("broken\r\npipe \\ 1".to_string()))
+            .unwrap();
+        let (_, capture) = recorder.into_parts();
+        assert_eq!(String::from_utf8_lossy(&capture).lines().count(), 2);
+
+        let mut replay = Replay::from_reader(&capture[..]).unwrap();
+        let err = replay.read_bytes(1).unwrap_err();
+        assert!(err.to_string().ends_with("replayed: broken\r\npipe \\ 1"));
+    }
+
+    #[test]
+    fn line_control_replayed() {
+        let mut recorder = Recorder::new(Simulator::new(), vec![]).unwrap();
+        recorder.set_dtr(true).unwrap();
+        recorder.clear_input().unwrap();
+        recorder.set_baud_rate(2_000_000).unwrap();
+        let (_, capture) = recorder.into_parts();
+
+        let mut replay = Replay::from_reader(&capture[..]).unwrap();
+        assert!(replay.can_set_baud_rate());
+        replay.set_dtr(true).unwrap();
+        replay.clear_input().unwrap();
+        let err = replay.set_baud_rate(115_200).unwrap_err();
+        assert!(err.to_string().contains("mismatch"));
+
+        let mut replay = Replay::from_reader(&capture[..]).unwrap();
+        let err = replay.clear_input().unwrap_err();
+        assert!(err.to_string().contains("mismatch"));
+    }
+
+    #[test]
+    fn line_control_failure_replayed() {
+        let mut sim = Simulator::new();
+        sim.fixed_baud_rate = true;
+        let mut recorder = Recorder::new(sim, vec![]).unwrap();
+        recorder.set_baud_rate(2_000_000).unwrap_err();
+        let (_, capture) = recorder.into_parts();
+
+        let mut replay = Replay::from_reader(&capture[..]).unwrap();
+        assert!(!replay.can_set_baud_rate());
+        let err = replay.set_baud_rate(2_000_000).unwrap_err();
+        assert!(err.to_string().contains("replayed: "));
+    }
+
+    #[test]
+    fn v1_accepts_line_control() {
+        let capture = "# bl capture v1\n 1 W 5555\n";
+        let mut replay = Replay::from_reader(capture.as_bytes()).unwrap();
+        replay.set_baud_rate(2_000_000).unwrap();
+        replay.clear_input().unwrap();
+        replay.write_bytes(&[0x55, 0x55]).unwrap();
+        assert!(replay.is_finished());
+    }
+
+    #[test]
+    fn replay_timeout() {
+        let capture = "# bl capture v1\n 1 W 05000000\n 4 T\n";
+        let mut replay = Replay::from_reader(capture.as_bytes()).unwrap();
+        let err = replay.send_command(commands::GetChipId).unwrap_err();
+        assert!(matches!(err, Error::Timeout));
+    }
+}
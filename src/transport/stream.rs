@@ -0,0 +1,188 @@
+//! ISP over any byte stream.
+//!
+//! [`IoTransport`] speaks the protocol over anything implementing
+//! [`Read`] + [`Write`]: a pty, a socket, a USB CDC device opened as a plain
+//! file or the stdio of a helper process. Streams should either block with a
+//! read timeout or be non-blocking, reads are bound by the transport deadline
+//! in both cases.
+
+use std::io::{self, Read, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::{HexDump, Transport};
+use crate::commands::DEFAULT_TIMEOUT;
+use crate::error::{Error, Result};
+
+pub struct IoTransport<S> {
+    inner: S,
+    timeout: Duration,
+}
+
+impl<S: Read + Write> IoTransport<S> {
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: Read + Write> Transport for IoTransport<S> {
+    fn read_bytes(&mut self, n: usize) -> Result<Vec<u8>> {
+        let mut buf = vec![0u8; n];
+        let deadline = Instant::now() + self.timeout;
+        let mut nread = 0;
+        while nread < n {
+            match self.inner.read(&mut buf[nread..]) {
+                Ok(0) => return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+                Ok(k) => nread += k,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                    thread::sleep(Duration::from_millis(1))
+                }
+                Err(e)
+                    if e.kind() == io::ErrorKind::TimedOut
+                        || e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+            if nread < n && Instant::now() >= deadline {
+                return Err(Error::Timeout);
+            }
+        }
+        log::trace!("read {} bytes\n{}", n, HexDump(&buf));
+        Ok(buf)
+    }
+
+    fn write_bytes(&mut self, buf: &[u8]) -> Result<()> {
+        self.inner.write_all(buf)?;
+        self.inner.flush()?;
+        log::trace!("write {} bytes\n{}", buf.len(), HexDump(buf));
+        Ok(())
+    }
+
+    fn set_timeout(&mut self, timeout: Duration) -> Result<()> {
+        self.timeout = timeout;
+        Ok(())
+    }
+}
+
+/// Joins a separate reader and writer into one stream, e.g. the stdout and
+/// stdin of a child process
+pub struct Duplex<R, W> {
+    pub reader: R,
+    pub writer: W,
+}
+
+impl<R, W> Duplex<R, W> {
+    pub fn new(reader: R, writer: W) -> Self {
+        Self { reader, writer }
+    }
+}
+
+impl<R: Read, W> Read for Duplex<R, W> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.reader.read(buf)
+    }
+}
+
+impl<R, W: Write> Write for Duplex<R, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands;
+    use crate::session::{Session, SyncOptions};
+    use crate::sim::Simulator;
+
+    /// Serve a simulated chip on the far end of `stream` until it closes
+    fn spawn_device<S: Read + Write + Send + 'static>(mut stream: S) -> thread::JoinHandle<()> {
+        thread::spawn(move || {
+            let mut sim = Simulator::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = match stream.read(&mut buf) {
+                    Ok(0) => return,
+                    Ok(n) => n,
+                    Err(e) if e.kind() == io::ErrorKind::TimedOut => continue,
+                    Err(_) => return,
+                };
+                sim.write_bytes(&buf[..n]).unwrap();
+                let reply = sim.read_bytes(sim.available()).unwrap();
+                if stream.write_all(&reply).is_err() {
+                    return;
+                }
+            }
+        })
+    }
+
+    fn read_flash<T: Transport>(transport: T) {
+        let mut session = Session::connect(transport, &SyncOptions::default()).unwrap();
+        assert_eq!(session.chip_id(), "CHIPWB03A00_BL");
+        let data = session
+            .send_command(commands::FlashRead {
+                start_addr: 0x1000,
+                len: 256,
+            })
+            .unwrap();
+        assert_eq!(data, vec![0xff; 256]);
+    }
+
+    #[test]
+    fn duplex_send_command() {
+        let reply: &[u8] = b"OK\x04\x00\x00\x00\x10\x00";
+        let mut transport = IoTransport::new(Duplex::new(reply, vec![]));
+        assert_eq!(
+            transport.send_command(commands::GetChipId).unwrap(),
+            "\x00\x00\x10\x00"
+        );
+        assert_eq!(transport.get_ref().writer, [0x05, 0x00, 0x00, 0x00]);
+        // reader exhausted
+        assert!(matches!(transport.read_bytes(2), Err(Error::Io(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unix_socket() {
+        use std::os::unix::net::UnixStream;
+
+        let (host, device) = UnixStream::pair().unwrap();
+        host.set_read_timeout(Some(Duration::from_millis(50)))
+            .unwrap();
+        let handle = spawn_device(device);
+        read_flash(IoTransport::new(host));
+        handle.join().unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn pty_pair() {
+        use serialport::{SerialPort, TTYPort};
+
+        let (mut host, mut device) = TTYPort::pair().unwrap();
+        host.set_timeout(Duration::from_millis(50)).unwrap();
+        device.set_timeout(Duration::from_millis(50)).unwrap();
+        let handle = spawn_device(device);
+        read_flash(IoTransport::new(host));
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,85 @@
+use std::process::Command;
+
+use bl::commands;
+use bl::fw_header::FwHeaderBuilder;
+use bl::session::{Session, SyncOptions};
+use bl::sim::Simulator;
+use bl::transport::Recorder;
+
+/// Capture of connecting with the default options, then running `f`
+fn record(sim: Simulator, f: impl FnOnce(&mut Session<Recorder<Simulator, Vec<u8>>>)) -> Vec<u8> {
+    let recorder = Recorder::new(sim, vec![]).unwrap();
+    let mut session = Session::connect(recorder, &SyncOptions::default()).unwrap();
+    session.switch_baud_rate(2_000_000).unwrap();
+    f(&mut session);
+    session.into_inner().into_parts().1
+}
+
+/// Capture of the exchanges `bl log` makes with the default options
+fn record_log_read(log: &str) -> Vec<u8> {
+    let mut sim = Simulator::new();
+    sim.log = log.to_string();
+    record(sim, |session| {
+        session.send_command(commands::LogRead).unwrap();
+    })
+}
+
+#[test]
+fn log_json() {
+    let capture = std::env::temp_dir().join(format!("bl-log-json-{}.txt", std::process::id()));
+    std::fs::write(&capture, record_log_read("boot from flash\nhello\n")).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_bl"))
+        .arg("--replay")
+        .arg(&capture)
+        .args(["log", "--json"])
+        .output()
+        .unwrap();
+    std::fs::remove_file(&capture).unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // all of stdout, nothing printed around the document
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let entries = json["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["event"], "boot_source");
+    assert_eq!(entries[0]["source"], "flash");
+    assert_eq!(entries[1]["text"], "hello");
+}
+
+#[test]
+fn flash_rejects_header_sharing_image_sector() {
+    let dir = std::env::temp_dir();
+    let id = std::process::id();
+    let capture = dir.join(format!("bl-flash-overlap-{}.txt", id));
+    let firmware = dir.join(format!("bl-flash-overlap-{}.bin", id));
+    let header = dir.join(format!("bl-flash-overlap-{}-header.bin", id));
+    let image = vec![0x5a; 64];
+    std::fs::write(&capture, record(Simulator::new(), |_| {})).unwrap();
+    std::fs::write(&firmware, &image).unwrap();
+    let raw = FwHeaderBuilder::new(&image)
+        .group_image_offset(0x800)
+        .build()
+        .to_bytes();
+    std::fs::write(&header, raw).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_bl"))
+        .arg("--replay")
+        .arg(&capture)
+        .arg("flash")
+        .arg(&firmware)
+        .arg("--boot-header")
+        .arg(&header)
+        .output()
+        .unwrap();
+    for path in [&capture, &firmware, &header] {
+        std::fs::remove_file(path).unwrap();
+    }
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("shares an erase sector"), "{}", stderr);
+}